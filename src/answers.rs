@@ -0,0 +1,103 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{collections::BTreeMap, fs, io};
+
+/// Answers for the real inputs, written by `cargo all --record`.
+pub const ANSWERS_PATH: &str = "src/answers.txt";
+
+const HEADER: &str =
+    "# Recorded answers, one `<day> <part> <answer>` per line. Written by `cargo all --record`.";
+
+/// Answers keyed by `(day, part)`.
+pub type Answers = BTreeMap<(u8, u8), String>;
+
+/// Multi-line answers are stored on one line, with `\n` for line breaks.
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut out = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            (c, _) => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn parse(store: &str) -> Answers {
+    store
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .filter_map(|l| {
+            let mut fields = l.splitn(3, ' ');
+            let day = fields.next()?.parse().ok()?;
+            let part = fields.next()?.parse().ok()?;
+            let answer = unescape(fields.next()?);
+            Some(((day, part), answer))
+        })
+        .collect()
+}
+
+pub fn render(answers: &Answers) -> String {
+    let mut out = format!("{}\n", HEADER);
+    for ((day, part), answer) in answers {
+        out.push_str(&format!("{:02} {} {}\n", day, part, escape(answer)));
+    }
+    out
+}
+
+/// Reads the store, failing if it does not exist.
+pub fn read() -> io::Result<Answers> {
+    fs::read_to_string(ANSWERS_PATH).map(|store| parse(&store))
+}
+
+/// Reads the store, treating a missing file as no answers.
+pub fn load() -> Answers {
+    read().unwrap_or_default()
+}
+
+/// Adds `new` to the store, replacing previously recorded answers for the same parts.
+pub fn record(new: Answers) -> io::Result<()> {
+    let mut answers = load();
+    answers.extend(new);
+    fs::write(ANSWERS_PATH, render(&answers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_render() {
+        let answers = parse("# comment\n07 1 95437\n05 2 MCD\n5 1 hello world\ninvalid\n");
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[&(5, 1)], "hello world");
+        assert_eq!(answers[&(7, 1)], "95437");
+        assert_eq!(parse(&render(&answers)), answers);
+    }
+
+    #[test]
+    fn test_multi_line_answers() {
+        let mut answers = Answers::new();
+        answers.insert((10, 2), "##..\n#..#".into());
+        answers.insert((11, 1), "C:\\n".into());
+
+        let store = render(&answers);
+        assert!(store.contains("10 2 ##..\\n#..#\n"));
+        assert_eq!(parse(&store), answers);
+    }
+}
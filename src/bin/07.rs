@@ -1,79 +1,214 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::BTreeMap, fmt::Display};
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut sizes = HashMap::new();
-    let mut affected = Vec::new();
+const ROOT: usize = 0;
 
-    for line in input.lines() {
-        if line.starts_with("$ ls") || line.starts_with("dir") {
-            continue;
+#[derive(Debug, Default)]
+pub struct Dir {
+    name: String,
+    parent: Option<usize>,
+    dirs: BTreeMap<String, usize>,
+    files: BTreeMap<String, u64>,
+}
+
+/// The directory tree seen in a terminal log. Directories are indices into `dirs`, `/` is `ROOT`.
+#[derive(Debug)]
+pub struct FileSystem {
+    dirs: Vec<Dir>,
+}
+
+impl FileSystem {
+    fn new() -> Self {
+        FileSystem {
+            dirs: vec![Dir {
+                name: "/".to_string(),
+                ..Dir::default()
+            }],
         }
+    }
 
-        let parts: Vec<_> = line.split_whitespace().collect();
-        match parts[..] {
-            ["$", "cd", ".."] => {
-                affected.pop();
-            }
-            ["$", "cd", name] => {
-                affected.push(name);
+    /// The subdirectory `name` of `dir`, created if it was not listed yet.
+    fn child(&mut self, dir: usize, name: &str) -> usize {
+        if let Some(&child) = self.dirs[dir].dirs.get(name) {
+            return child;
+        }
+        let child = self.dirs.len();
+        self.dirs.push(Dir {
+            name: name.to_string(),
+            parent: Some(dir),
+            ..Dir::default()
+        });
+        self.dirs[dir].dirs.insert(name.to_string(), child);
+        child
+    }
+
+    /// Looks up a directory by its absolute path, e.g. `/a/e`.
+    pub fn find(&self, path: &str) -> Option<usize> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(ROOT, |dir, name| self.dirs[dir].dirs.get(name).copied())
+    }
+
+    pub fn path(&self, dir: usize) -> String {
+        let mut names = Vec::new();
+        let mut curr = dir;
+        while let Some(parent) = self.dirs[curr].parent {
+            names.push(self.dirs[curr].name.as_str());
+            curr = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Total size of every directory, indexed like `dirs`.
+    pub fn sizes(&self) -> Vec<u64> {
+        let mut sizes: Vec<u64> = self.dirs.iter().map(|d| d.files.values().sum()).collect();
+        // children are always created after their parent.
+        for dir in (1..self.dirs.len()).rev() {
+            if let Some(parent) = self.dirs[dir].parent {
+                sizes[parent] += sizes[dir];
             }
-            [size, _name] => {
-                let size: u32 = size.parse().unwrap();
-                for idx in 0..affected.len() {
-                    let path = PathBuf::from_iter(&affected[0..=idx]);
-                    *sizes.entry(path).or_insert(0) += size;
+        }
+        sizes
+    }
+
+    pub fn size(&self, dir: usize) -> u64 {
+        self.sizes()[dir]
+    }
+
+    /// Directories with a total size of at most `limit`.
+    pub fn dirs_at_most(&self, limit: u64) -> Vec<(usize, u64)> {
+        self.sizes()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, size)| *size <= limit)
+            .collect()
+    }
+
+    /// The smallest directory to delete so that `needed` is free on a disk of `disk`.
+    /// `None` if there is enough free space already or no directory is large enough.
+    pub fn smallest_deletion(&self, disk: u64, needed: u64) -> Option<(usize, u64)> {
+        let sizes = self.sizes();
+        let free = disk.saturating_sub(sizes[ROOT]);
+        let missing = needed.checked_sub(free).filter(|missing| *missing > 0)?;
+        sizes
+            .into_iter()
+            .enumerate()
+            .filter(|(_, size)| *size >= missing)
+            .min_by_key(|(_, size)| *size)
+    }
+
+    fn render(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        dir: usize,
+        sizes: &[u64],
+        prefix: &str,
+    ) -> std::fmt::Result {
+        enum Entry<'a> {
+            Dir(usize),
+            File(&'a str, u64),
+        }
+
+        let mut entries: Vec<(&str, Entry)> = self.dirs[dir]
+            .dirs
+            .iter()
+            .map(|(name, child)| (name.as_str(), Entry::Dir(*child)))
+            .chain(
+                self.dirs[dir]
+                    .files
+                    .iter()
+                    .map(|(name, size)| (name.as_str(), Entry::File(name, *size))),
+            )
+            .collect();
+        entries.sort_by_key(|(name, _)| *name);
+
+        for (idx, (_, entry)) in entries.iter().enumerate() {
+            let last = idx == entries.len() - 1;
+            let branch = if last { "└── " } else { "├── " };
+            match entry {
+                Entry::Dir(child) => {
+                    let name = &self.dirs[*child].name;
+                    writeln!(
+                        f,
+                        "{}{}{} (dir, size={})",
+                        prefix, branch, name, sizes[*child]
+                    )?;
+                    let indent = if last { "    " } else { "│   " };
+                    self.render(f, *child, sizes, &format!("{}{}", prefix, indent))?;
+                }
+                Entry::File(name, size) => {
+                    writeln!(f, "{}{}{} (file, size={})", prefix, branch, name, size)?
                 }
             }
-            _ => panic!("unexpected line"),
-        };
+        }
+        Ok(())
     }
-
-    Some(sizes.into_values().filter(|size| *size < 100_000).sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut sizes = HashMap::new();
-    let mut affected = Vec::new();
+impl Display for FileSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sizes = self.sizes();
+        writeln!(f, "/ (dir, size={})", sizes[ROOT])?;
+        self.render(f, ROOT, &sizes, "")
+    }
+}
 
-    for line in input.lines() {
-        if line.starts_with("$ ls") || line.starts_with("dir") {
-            continue;
-        }
+pub fn parse(input: &str) -> Result<FileSystem, String> {
+    let mut fs = FileSystem::new();
+    let mut cwd = ROOT;
 
+    for (idx, line) in input.lines().enumerate() {
         let parts: Vec<_> = line.split_whitespace().collect();
         match parts[..] {
-            ["$", "cd", ".."] => {
-                affected.pop();
-            }
-            ["$", "cd", name] => {
-                affected.push(name);
+            ["$", "cd", "/"] => cwd = ROOT,
+            ["$", "cd", ".."] => cwd = fs.dirs[cwd].parent.unwrap_or(ROOT),
+            ["$", "cd", name] => cwd = fs.child(cwd, name),
+            ["$", "ls"] | [] => {}
+            ["dir", name] => {
+                fs.child(cwd, name);
             }
-            [size, _name] => {
-                let size: u32 = size.parse().unwrap();
-                for idx in 0..affected.len() {
-                    let path = PathBuf::from_iter(&affected[0..=idx]);
-                    *sizes.entry(path).or_insert(0) += size;
-                }
+            [size, name] => {
+                let size = size
+                    .parse()
+                    .map_err(|_| format!("line {}: invalid size \"{}\"", idx + 1, size))?;
+                // listing a directory again overwrites instead of counting twice.
+                fs.dirs[cwd].files.insert(name.to_string(), size);
             }
-            _ => panic!("unexpected line"),
+            _ => return Err(format!("line {}: unexpected \"{}\"", idx + 1, line)),
         };
     }
 
-    let disk = 70_000_000;
-    let needed = 30_000_000;
-    let root = sizes.get(&PathBuf::from("/")).unwrap();
-    let available = disk - root;
+    Ok(fs)
+}
 
-    sizes
-        .into_values()
-        .filter(|size| *size >= needed - available)
-        .min()
+pub fn part_one(input: &str) -> Option<u64> {
+    let fs = parse(input).ok()?;
+    Some(fs.dirs_at_most(100_000).iter().map(|(_, size)| size).sum())
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let fs = parse(input).ok()?;
+    let (_, size) = fs.smallest_deletion(70_000_000, 30_000_000)?;
+    Some(size)
 }
 
 fn main() {
+    let tree = pico_args::Arguments::from_env().contains("--tree");
+
     let input = &advent_of_code::read_file("inputs", 7);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
+
+    // `cargo solve 07 -- --tree` prints the directory tree with the sizes behind the answers.
+    if tree {
+        match parse(input) {
+            Ok(fs) => print!("{}", fs),
+            Err(e) => {
+                eprintln!("Failed to parse input: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -91,4 +226,52 @@ mod tests {
         let input = advent_of_code::read_file("examples", 7);
         assert_eq!(part_two(&input), Some(24933642));
     }
+
+    #[test]
+    fn test_sizes() {
+        let input = advent_of_code::read_file("examples", 7);
+        let fs = parse(&input).unwrap();
+        assert_eq!(fs.size(ROOT), 48381165);
+        assert_eq!(fs.size(fs.find("/a").unwrap()), 94853);
+        assert_eq!(fs.size(fs.find("/a/e").unwrap()), 584);
+        assert_eq!(fs.size(fs.find("/d/").unwrap()), 24933642);
+        assert_eq!(fs.find("/a/x"), None);
+        assert_eq!(fs.path(fs.find("/a/e").unwrap()), "/a/e");
+        assert_eq!(fs.path(ROOT), "/");
+    }
+
+    #[test]
+    fn test_cd_root_and_repeated_ls() {
+        let input = "$ cd /\n$ ls\ndir a\n10 x\n$ cd a\n$ ls\n20 y\n$ cd /\n$ ls\ndir a\n10 x\n$ cd a\n$ ls\n20 y\n$ cd ..\n$ cd ..\n";
+        let fs = parse(input).unwrap();
+        assert_eq!(fs.size(ROOT), 30);
+        assert_eq!(fs.dirs.len(), 2);
+
+        assert!(parse("$ cd /\nfoo\n").is_err());
+        assert!(parse("$ cd /\nabc x\n").is_err());
+    }
+
+    #[test]
+    fn test_smallest_deletion() {
+        let input = advent_of_code::read_file("examples", 7);
+        let fs = parse(&input).unwrap();
+        let d = fs.find("/d").unwrap();
+        assert_eq!(
+            fs.smallest_deletion(70_000_000, 30_000_000),
+            Some((d, 24933642))
+        );
+        let e = fs.find("/a/e").unwrap();
+        assert_eq!(fs.smallest_deletion(48_381_165, 500), Some((e, 584)));
+        assert_eq!(fs.smallest_deletion(100_000_000, 30_000_000), None);
+        assert_eq!(fs.smallest_deletion(10, 100_000_000), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let fs = parse("$ cd /\n$ ls\ndir a\n5 b.txt\n$ cd a\n$ ls\n3 c\n").unwrap();
+        assert_eq!(
+            fs.to_string(),
+            "/ (dir, size=8)\n├── a (dir, size=3)\n│   └── c (file, size=3)\n└── b.txt (file, size=5)\n"
+        );
+    }
 }
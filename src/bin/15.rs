@@ -49,97 +49,106 @@ pub fn parse(input: &str) -> Input {
         .collect()
 }
 
-pub fn get_lines(sensor: &Sensor) -> Vec<isize> {
-    let dist = sensor.at.manhattan_distance(&sensor.closest_beacon);
-    vec![
-        sensor.at.y - sensor.at.x - dist,
-        sensor.at.y - sensor.at.x + dist,
-        sensor.at.y + sensor.at.x + dist,
-        sensor.at.y + sensor.at.x - dist,
-    ]
-}
+impl Sensor {
+    /// Distance to the closest beacon. No other beacon is within this distance.
+    pub fn radius(&self) -> isize {
+        self.at.manhattan_distance(&self.closest_beacon)
+    }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let input = parse(input);
-    let target_row = if cfg!(test) { 10 } else { 2000000 };
-    let mut beacons = HashSet::new();
-    input.iter().for_each(|sensor| {
-        let max_distance = sensor.at.manhattan_distance(&sensor.closest_beacon);
-        let max_y = sensor.at.y + max_distance;
-        let min_y = sensor.at.y - max_distance;
-
-        if min_y <= target_row && target_row <= max_y {
-            let min_x = sensor.at.x - ((sensor.at.y - target_row).abs() - max_distance).abs();
-            let max_x = sensor.at.x + ((sensor.at.y - target_row).abs() - max_distance).abs();
-            for x in min_x..max_x {
-                beacons.insert((x, target_row));
-            }
+    /// Inclusive range of x covered by this sensor in row `y`, if any.
+    pub fn row_range(&self, y: isize) -> Option<(isize, isize)> {
+        let reach = self.radius() - (self.at.y - y).abs();
+        if reach < 0 {
+            return None;
         }
-    });
-    Some(beacons.len())
+        Some((self.at.x - reach, self.at.x + reach))
+    }
 }
 
-pub fn is_admissible(p: Point) -> bool {
-    0 <= p.x && p.x <= 4000000 && 0 <= p.y && p.y <= 4000000
-}
+/// Coverage queries over a set of sensors. A cell is covered when it is within the radius of any sensor.
+pub trait Coverage {
+    /// Inclusive ranges of x covered in row `y`, sorted and merged so that they neither overlap nor touch.
+    fn row_coverage(&self, y: isize) -> Vec<(isize, isize)>;
 
-pub fn part_two(input: &str) -> Option<isize> {
-    let input = parse(input);
-    let mut intersections = vec![];
-    let lines: Vec<Vec<isize>> = input.iter().map(get_lines).collect();
-    lines.iter().for_each(|line1| {
-        lines.iter().for_each(|line2| {
-            for ord1 in line1.iter().take(2) {
-                for ord2 in line2.iter().take(4).skip(2) {
-                    let b1 = ord1;
-                    let b2 = ord2;
-
-                    if (b1 + b2) % 2 != 0 {
-                        continue;
-                    }
-
-                    let x = (b2 - b1) / 2;
-                    let y = (b2 + b1) / 2;
-                    if is_admissible(Point { x, y }) && !intersections.contains(&Point { x, y }) {
-                        intersections.push(Point { x, y });
-                    }
+    /// Number of covered cells in row `y`, including known beacons.
+    fn covered_in_row(&self, y: isize) -> usize {
+        self.row_coverage(y)
+            .iter()
+            .map(|(start, end)| (end - start + 1) as usize)
+            .sum()
+    }
+
+    fn is_covered(&self, p: &Point) -> bool {
+        self.row_coverage(p.y)
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&p.x))
+    }
+
+    /// Uncovered cells in the rectangle spanned by `from` and `to` (inclusive), row by row.
+    fn uncovered_in(&self, from: &Point, to: &Point) -> impl Iterator<Item = Point> {
+        let (min_x, max_x) = (from.x, to.x);
+        (from.y..=to.y).flat_map(move |y| {
+            let mut gaps = Vec::new();
+            let mut x = min_x;
+            for (start, end) in self.row_coverage(y) {
+                if start > max_x {
+                    break;
+                }
+                if start > x {
+                    gaps.push((x, start - 1));
                 }
+                x = x.max(end + 1);
             }
-        });
-    });
-
-    let ndist2: Vec<usize> = intersections
-        .iter()
-        .map(|p1| {
-            intersections
-                .iter()
-                .filter(|p2| p1 != *p2 && p1.manhattan_distance(p2) == 2)
-                .count()
+            if x <= max_x {
+                gaps.push((x, max_x));
+            }
+            gaps.into_iter()
+                .flat_map(move |(start, end)| (start..=end).map(move |x| Point { x, y }))
         })
-        .collect();
+    }
+}
 
-    let candidates: Vec<Point> = intersections
-        .iter()
-        .enumerate()
-        .filter(|(i, _)| ndist2[*i] >= 3)
-        .map(|(_, p)| p.clone())
-        .collect();
+impl Coverage for [Sensor] {
+    fn row_coverage(&self, y: isize) -> Vec<(isize, isize)> {
+        let mut ranges: Vec<(isize, isize)> = self.iter().filter_map(|s| s.row_range(y)).collect();
+        ranges.sort_unstable();
 
-    for candidate in &candidates {
-        if candidates.contains(&Point {
-            x: candidate.x + 2,
-            y: candidate.y,
-        }) && candidates.contains(&Point {
-            x: candidate.x + 1,
-            y: candidate.y + 1,
-        }) && candidates.contains(&Point {
-            x: candidate.x + 1,
-            y: candidate.y - 1,
-        }) {
-            return Some((candidate.x + 1) * 4000000 + candidate.y);
+        let mut merged: Vec<(isize, isize)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some((_, last_end)) if start <= *last_end + 1 => *last_end = (*last_end).max(end),
+                _ => merged.push((start, end)),
+            }
         }
+        merged
     }
-    None
+}
+
+/// Positions in row `y` that cannot contain a beacon.
+fn positions_without_beacon(sensors: &[Sensor], y: isize) -> usize {
+    // every beacon is covered by the sensor that reports it, so it is counted exactly once.
+    let beacons: HashSet<&Point> = sensors
+        .iter()
+        .map(|sensor| &sensor.closest_beacon)
+        .filter(|beacon| beacon.y == y)
+        .collect();
+    sensors.covered_in_row(y) - beacons.len()
+}
+
+/// Tuning frequency of the only position within `0..=bound` on both axes that no sensor covers.
+fn tuning_frequency(sensors: &[Sensor], bound: isize) -> Option<isize> {
+    let beacon = sensors
+        .uncovered_in(&Point { x: 0, y: 0 }, &Point { x: bound, y: bound })
+        .next()?;
+    Some(beacon.x * 4000000 + beacon.y)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(positions_without_beacon(&parse(input), 2000000))
+}
+
+pub fn part_two(input: &str) -> Option<isize> {
+    tuning_frequency(&parse(input), 4000000)
 }
 
 fn main() {
@@ -155,12 +164,113 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(part_one(&input), Some(26));
+        assert_eq!(positions_without_beacon(&parse(&input), 10), 26);
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(part_two(&input), Some(40000017));
+        assert_eq!(tuning_frequency(&parse(&input), 20), Some(56000011));
+    }
+
+    fn sensor(x: isize, y: isize, beacon_x: isize, beacon_y: isize) -> Sensor {
+        Sensor {
+            at: Point { x, y },
+            closest_beacon: Point {
+                x: beacon_x,
+                y: beacon_y,
+            },
+        }
+    }
+
+    fn corner(sensors: &[Sensor], bound: isize) -> Option<Point> {
+        sensors
+            .uncovered_in(&Point { x: 0, y: 0 }, &Point { x: bound, y: bound })
+            .next()
+    }
+
+    #[test]
+    fn test_uncovered_on_boundary() {
+        // a 5x5 area covered everywhere except for the corner (4, 4).
+        let sensors = vec![sensor(0, 0, 0, 7), sensor(4, 0, 4, 3), sensor(0, 4, 3, 4)];
+        assert_eq!(corner(&sensors, 4), Some(Point { x: 4, y: 4 }));
+
+        // only the origin is uncovered.
+        let sensors = vec![sensor(4, 4, 4, 0), sensor(1, 3, 0, 1), sensor(3, 1, 1, 0)];
+        assert_eq!(corner(&sensors, 4), Some(Point { x: 0, y: 0 }));
+
+        // the left edge of a row in the middle.
+        let sensors = vec![
+            sensor(2, 0, 2, 4),
+            sensor(2, 4, 2, 8),
+            sensor(2, 2, 4, 2),
+            sensor(1, 2, -1, 2),
+        ];
+        assert_eq!(corner(&sensors, 4), None);
+        let sensors = vec![sensor(2, 0, 2, 3), sensor(2, 4, 2, 7), sensor(3, 2, 5, 2)];
+        assert_eq!(corner(&sensors, 4), Some(Point { x: 0, y: 2 }));
+    }
+
+    #[test]
+    fn test_row_coverage() {
+        // (-2, 2) and (3, 7) touch and are merged, (19, 21) stays separate.
+        let sensors = [sensor(0, 0, 2, 0), sensor(5, 0, 7, 0), sensor(20, 0, 20, 1)];
+        assert_eq!(sensors.row_coverage(0), vec![(-2, 7), (19, 21)]);
+        assert_eq!(sensors.row_coverage(2), vec![(0, 0), (5, 5)]);
+        assert!(sensors.row_coverage(5).is_empty());
+
+        assert_eq!(sensors.covered_in_row(0), 13);
+        assert_eq!(sensors.covered_in_row(1), 3 + 3 + 1);
+        assert_eq!(sensors.covered_in_row(5), 0);
+    }
+
+    #[test]
+    fn test_positions_without_beacon() {
+        // the beacons at (2, 0), (7, 0) and (20, 1) are covered but not counted.
+        let sensors = [sensor(0, 0, 2, 0), sensor(5, 0, 7, 0), sensor(20, 0, 20, 1)];
+        assert_eq!(positions_without_beacon(&sensors, 0), 13 - 2);
+        assert_eq!(positions_without_beacon(&sensors, 1), 7 - 1);
+        assert_eq!(positions_without_beacon(&sensors, 5), 0);
+    }
+
+    #[test]
+    fn test_tuning_frequency() {
+        let sensors = [sensor(0, 0, 0, 7), sensor(4, 0, 4, 3), sensor(0, 4, 3, 4)];
+        assert_eq!(tuning_frequency(&sensors, 4), Some(4 * 4000000 + 4));
+        assert_eq!(tuning_frequency(&sensors, 3), None);
+    }
+
+    #[test]
+    fn test_is_covered() {
+        let sensors = [sensor(0, 0, 2, 0), sensor(10, 10, 10, 11)];
+        assert!(sensors.is_covered(&Point { x: 0, y: 0 }));
+        assert!(sensors.is_covered(&Point { x: 2, y: 0 }));
+        assert!(sensors.is_covered(&Point { x: -1, y: -1 }));
+        assert!(!sensors.is_covered(&Point { x: 2, y: 1 }));
+        assert!(sensors.is_covered(&Point { x: 10, y: 9 }));
+        assert!(!sensors.is_covered(&Point { x: 5, y: 5 }));
+    }
+
+    #[test]
+    fn test_uncovered_in() {
+        let sensors = [sensor(1, 1, 2, 1)];
+        let uncovered: Vec<Point> = sensors
+            .uncovered_in(&Point { x: 0, y: 0 }, &Point { x: 2, y: 2 })
+            .collect();
+        assert_eq!(
+            uncovered,
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 2, y: 0 },
+                Point { x: 0, y: 2 },
+                Point { x: 2, y: 2 },
+            ]
+        );
+        assert_eq!(
+            sensors
+                .uncovered_in(&Point { x: 10, y: 0 }, &Point { x: 14, y: 1 })
+                .count(),
+            10
+        );
     }
 }
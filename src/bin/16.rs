@@ -2,6 +2,8 @@ use itertools::Itertools;
 use std::{
     cmp::Ordering,
     collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::Display,
+    time::Instant,
 };
 
 #[derive(Eq, PartialEq)]
@@ -16,12 +18,16 @@ pub struct Node<'a> {
     curr: &'a str,
 }
 
+/// Valves opened by a single agent, with the minute during which each is opened.
+pub type Route<'a> = Vec<(u32, &'a str)>;
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub struct State<'a> {
     opened: BTreeSet<&'a str>,
     curr: &'a str,
     elapsed: u32,
     relieved: u32,
+    route: Route<'a>,
 }
 
 impl<'a> Ord for Node<'a> {
@@ -63,7 +69,7 @@ fn min_cost(from: &str, to: &str, map: &HashMap<&str, Valve>) -> u32 {
     u32::MAX
 }
 
-pub fn parse(input: &str) -> HashMap<&str, Valve> {
+pub fn parse(input: &str) -> HashMap<&str, Valve<'_>> {
     input
         .lines()
         .map(|line| {
@@ -81,17 +87,20 @@ pub fn parse(input: &str) -> HashMap<&str, Valve> {
         .collect()
 }
 
-pub fn min_distance<'a>(map: &'a HashMap<&str, Valve>) -> HashMap<(&'a str, &'a str), u32> {
+pub fn min_distance<'a>(
+    map: &'a HashMap<&str, Valve>,
+    start: &'a str,
+) -> HashMap<(&'a str, &'a str), u32> {
     map.iter()
         .filter(|(_, valve)| valve.flow > 0)
         .map(|(name, _)| name)
         .tuple_combinations()
         .fold(HashMap::new(), |mut acc, (name1, name2)| {
-            acc.entry(("AA", name1))
-                .or_insert_with(|| min_cost("AA", name1, map));
+            acc.entry((start, name1))
+                .or_insert_with(|| min_cost(start, name1, map));
 
-            acc.entry(("AA", name2))
-                .or_insert_with(|| min_cost("AA", name2, map));
+            acc.entry((start, name2))
+                .or_insert_with(|| min_cost(start, name2, map));
 
             let dist = min_cost(name1, name2, map);
 
@@ -113,24 +122,32 @@ pub fn wait_until_ending(
     let relieved_per_min: u32 = opened.iter().map(|name| map[name].flow).sum();
     relieved + time_left * relieved_per_min
 }
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = parse(input);
-    let dist_map = min_distance(&map);
+
+/// Most pressure a single agent can relieve within `minutes` for every set of valves it can open,
+/// with the route achieving it.
+pub fn max_relieved_per_set<'a>(
+    map: &'a HashMap<&str, Valve>,
+    start: &'a str,
+    minutes: u32,
+) -> HashMap<BTreeSet<&'a str>, (u32, Route<'a>)> {
+    let dist_map = min_distance(map, start);
     let flowing: HashSet<_> = map
         .iter()
         .filter(|(_, valve)| valve.flow > 0)
         .map(|(&name, _)| name)
         .collect();
 
-    let mut max_relieved = 0;
     let mut q = VecDeque::new();
     let mut seen = HashSet::new();
 
+    let mut max_relieves_states: HashMap<BTreeSet<&str>, (u32, Route)> = HashMap::new();
+
     q.push_back(State {
-        curr: "AA",
+        curr: start,
         opened: BTreeSet::new(),
         elapsed: 0,
         relieved: 0,
+        route: vec![],
     });
 
     seen.insert((BTreeSet::new(), 0, 0));
@@ -140,11 +157,18 @@ pub fn part_one(input: &str) -> Option<u32> {
         opened,
         elapsed,
         relieved,
+        route,
     }) = q.pop_front()
     {
-        if opened.len() == flowing.len() || elapsed >= 30 {
-            let relieved_at_end = wait_until_ending(30, elapsed, relieved, &opened, &map);
-            max_relieved = max_relieved.max(relieved_at_end);
+        let relieved_at_end = wait_until_ending(minutes, elapsed, relieved, &opened, map);
+        if max_relieves_states
+            .get(&opened)
+            .is_none_or(|(best, _)| *best < relieved_at_end)
+        {
+            max_relieves_states.insert(opened.clone(), (relieved_at_end, route.clone()));
+        }
+
+        if opened.len() == flowing.len() || elapsed >= minutes {
             continue;
         }
 
@@ -153,9 +177,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         for dest in unopened {
             let cost = dist_map[&(curr, *dest)] + 1;
             let new_elapsed = elapsed + cost;
-            if new_elapsed >= 30 {
-                let relieved_at_end = wait_until_ending(30, elapsed, relieved, &opened, &map);
-                max_relieved = max_relieved.max(relieved_at_end);
+            if new_elapsed >= minutes {
                 continue;
             }
 
@@ -166,99 +188,480 @@ pub fn part_one(input: &str) -> Option<u32> {
             new_opened.insert(dest);
 
             if seen.insert((new_opened.clone(), new_elapsed, new_relieved)) {
+                let mut new_route = route.clone();
+                new_route.push((new_elapsed, *dest));
                 q.push_back(State {
                     curr: dest,
                     opened: new_opened,
                     elapsed: new_elapsed,
                     relieved: new_relieved,
+                    route: new_route,
                 })
             }
         }
     }
-    Some(max_relieved)
+
+    max_relieves_states
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map = parse(input);
-    let dist_map = min_distance(&map);
-    let flowing: HashSet<_> = map
-        .iter()
-        .filter(|(_, valve)| valve.flow > 0)
-        .map(|(&name, _)| name)
-        .collect();
+/// An agent opening a valve. The valve releases `flow` from the minute after `minute` until the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<'a> {
+    pub minute: u32,
+    pub agent: usize,
+    pub valve: &'a str,
+    pub flow: u32,
+    /// total pressure released by this valve.
+    pub released: u32,
+}
 
-    // let mut max_relieved = 0;
-    let mut q = VecDeque::new();
-    let mut seen = HashSet::new();
+/// Optimal result of the agents working in parallel, with the valves opened in order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Plan<'a> {
+    pub relieved: u32,
+    pub schedule: Vec<Step<'a>>,
+}
 
-    let mut max_relieves_states: HashMap<BTreeSet<&str>, u32> = HashMap::new();
+impl<'a> Plan<'a> {
+    /// Valves opened by `agent`.
+    pub fn opened(&self, agent: usize) -> BTreeSet<&'a str> {
+        self.schedule
+            .iter()
+            .filter(|step| step.agent == agent)
+            .map(|step| step.valve)
+            .collect()
+    }
+}
 
-    q.push_back(State {
-        curr: "AA",
-        opened: BTreeSet::new(),
-        elapsed: 0,
-        relieved: 0,
-    });
+impl<'a> Display for Plan<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Minute  Agent  Valve  Flow  Released")?;
+        for step in &self.schedule {
+            writeln!(
+                f,
+                "{:>6}  {:>5}  {:>5}  {:>4}  {:>8}",
+                step.minute,
+                step.agent + 1,
+                step.valve,
+                step.flow,
+                step.released
+            )?;
+        }
+        write!(f, "Total released: {}", self.relieved)
+    }
+}
 
-    seen.insert((BTreeSet::new(), 0, 0));
+/// Reference solver exploring all states breadth-first, used to verify and benchmark [solve].
+/// Agents never open the same valve, so the best plan pairs up disjoint sets of opened valves.
+pub fn solve_bfs<'a>(
+    map: &'a HashMap<&str, Valve>,
+    start: &'a str,
+    agents: usize,
+    minutes: u32,
+) -> Plan<'a> {
+    let max_relieves_states = max_relieved_per_set(map, start, minutes);
+
+    // best plan per union of opened valves, adding one agent at a time.
+    let mut plans: HashMap<BTreeSet<&str>, Plan> =
+        HashMap::from([(BTreeSet::new(), Plan::default())]);
+
+    for agent in 0..agents {
+        let mut next: HashMap<BTreeSet<&str>, Plan> = HashMap::new();
+        for (union, plan) in &plans {
+            for (opened, (relieved, route)) in max_relieves_states
+                .iter()
+                .filter(|(opened, _)| union.is_disjoint(opened))
+            {
+                let total = plan.relieved + relieved;
+                let key: BTreeSet<_> = union.union(opened).copied().collect();
+                if next.get(&key).is_none_or(|best| best.relieved < total) {
+                    let mut schedule = plan.schedule.clone();
+                    schedule.extend(route.iter().map(|&(minute, valve)| Step {
+                        minute,
+                        agent,
+                        valve,
+                        flow: map[valve].flow,
+                        released: map[valve].flow * (minutes - minute),
+                    }));
+                    next.insert(
+                        key,
+                        Plan {
+                            relieved: total,
+                            schedule,
+                        },
+                    );
+                }
+            }
+        }
+        plans = next;
+    }
 
-    while let Some(State {
-        curr,
-        opened,
-        elapsed,
-        relieved,
-    }) = q.pop_front()
-    {
-        let relieved_at_end = wait_until_ending(26, elapsed, relieved, &opened, &map);
-        max_relieves_states
-            .entry(opened.clone())
-            .and_modify(|val| *val = relieved_at_end.max(*val))
-            .or_insert(relieved_at_end);
+    let mut plan = plans
+        .into_values()
+        .max_by_key(|plan| plan.relieved)
+        .unwrap_or_default();
+    plan.schedule.sort_by_key(|step| (step.minute, step.agent));
+    plan
+}
 
-        if opened.len() == flowing.len() || elapsed >= 26 {
-            continue;
+/// The valves with flow and the start valve, indexed so that sets of valves fit into a bit mask.
+pub struct Graph<'a> {
+    names: Vec<&'a str>,
+    flows: Vec<u32>,
+    dist: Vec<Vec<u32>>,
+    start: usize,
+    /// indices of the valves with flow, highest flow first.
+    by_flow: Vec<usize>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new(map: &'a HashMap<&str, Valve>, start: &'a str) -> Self {
+        let mut names: Vec<&str> = map
+            .iter()
+            .filter(|(_, valve)| valve.flow > 0)
+            .map(|(&name, _)| name)
+            .sorted()
+            .collect();
+
+        let start = match names.iter().position(|&name| name == start) {
+            Some(idx) => idx,
+            None => {
+                names.push(start);
+                names.len() - 1
+            }
+        };
+        // the start valve is part of the mask as well, even without flow.
+        assert!(
+            names.len() <= 64,
+            "too many valves with flow for a u64 mask"
+        );
+
+        let dist = names
+            .iter()
+            .map(|from| {
+                names
+                    .iter()
+                    .map(|to| {
+                        if from == to {
+                            0
+                        } else {
+                            min_cost(from, to, map)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let flows: Vec<u32> = names.iter().map(|name| map[name].flow).collect();
+        let by_flow = (0..names.len())
+            .filter(|&idx| flows[idx] > 0)
+            .sorted_by_key(|&idx| std::cmp::Reverse(flows[idx]))
+            .collect();
+
+        Graph {
+            names,
+            flows,
+            dist,
+            start,
+            by_flow,
         }
+    }
+}
 
-        let unopened = flowing.iter().filter(|name| !opened.contains(*name));
+/// Depth-first search over the routes of a single agent.
+struct Search<'g, 'a> {
+    graph: &'g Graph<'a>,
+    minutes: u32,
+    /// skip routes that cannot beat the best total. Only set for a single agent: part two is not pruned,
+    /// because [combine] needs the best route for every set of valves, not just the best one overall.
+    prune: bool,
+    best: HashMap<u64, (u32, Vec<(u32, usize)>)>,
+    best_total: u32,
+    route: Vec<(u32, usize)>,
+}
 
-        for dest in unopened {
-            let cost = dist_map[&(curr, *dest)] + 1;
-            let new_elapsed = elapsed + cost;
-            if new_elapsed >= 26 {
-                continue;
+impl<'g, 'a> Search<'g, 'a> {
+    /// Upper bound of the pressure released when continuing from `curr`: the remaining valves are
+    /// opened in order of their flow, as if each was one minute away.
+    fn bound(&self, curr: usize, elapsed: u32, opened: u64, relieved: u32) -> u32 {
+        let mut remaining = self.minutes - elapsed;
+        let mut bound = relieved;
+        // only the current valve can be opened without moving first.
+        let mut step = if opened & (1 << curr) == 0 { 1 } else { 2 };
+        for &idx in self
+            .graph
+            .by_flow
+            .iter()
+            .filter(|&&idx| opened & (1 << idx) == 0)
+        {
+            if remaining <= step {
+                break;
             }
+            remaining -= step;
+            bound += self.graph.flows[idx] * remaining;
+            step = 2;
+        }
+        bound
+    }
 
-            let relieved_per_min: u32 = opened.iter().map(|name| map[name].flow).sum();
-            let new_relieved = relieved + relieved_per_min * cost;
+    fn visit(&mut self, curr: usize, elapsed: u32, opened: u64, relieved: u32) {
+        if self
+            .best
+            .get(&opened)
+            .is_none_or(|(best, _)| *best < relieved)
+        {
+            self.best.insert(opened, (relieved, self.route.clone()));
+        }
+        self.best_total = self.best_total.max(relieved);
 
-            let mut new_opened = opened.clone();
-            new_opened.insert(dest);
+        if self.prune && self.bound(curr, elapsed, opened, relieved) <= self.best_total {
+            return;
+        }
 
-            if seen.insert((new_opened.clone(), new_elapsed, new_relieved)) {
-                q.push_back(State {
-                    curr: dest,
-                    opened: new_opened,
-                    elapsed: new_elapsed,
-                    relieved: new_relieved,
-                })
+        for &next in &self.graph.by_flow {
+            if opened & (1 << next) != 0 {
+                continue;
+            }
+            let minute = elapsed + self.graph.dist[curr][next] + 1;
+            if minute >= self.minutes {
+                continue;
             }
+            let released = self.graph.flows[next] * (self.minutes - minute);
+
+            self.route.push((minute, next));
+            self.visit(next, minute, opened | (1 << next), relieved + released);
+            self.route.pop();
         }
     }
-    Some(
-        max_relieves_states
-            .iter()
-            .tuple_combinations()
-            .filter(|(human, elephant)| human.0.is_disjoint(elephant.0))
-            .map(|(human, elephant)| human.1 + elephant.1)
-            .max()
-            .unwrap(),
-    )
+}
+
+/// Chooses one set of valves per agent from `entries`, sorted by pressure released in descending order.
+/// Agents are interchangeable, so they pick entries in that order, which also bounds the remaining agents.
+fn combine(
+    entries: &[(u64, u32)],
+    agents_left: usize,
+    from: usize,
+    used: u64,
+    total: u32,
+    chosen: &mut Vec<usize>,
+    best: &mut (u32, Vec<usize>),
+) {
+    if total > best.0 || best.1.is_empty() {
+        *best = (total, chosen.clone());
+    }
+    if agents_left == 0 {
+        return;
+    }
+
+    for (idx, &(mask, relieved)) in entries.iter().enumerate().skip(from) {
+        if total + relieved * agents_left as u32 <= best.0 && !best.1.is_empty() {
+            break;
+        }
+        if mask & used != 0 {
+            continue;
+        }
+        chosen.push(idx);
+        combine(
+            entries,
+            agents_left - 1,
+            idx,
+            used | mask,
+            total + relieved,
+            chosen,
+            best,
+        );
+        chosen.pop();
+    }
+}
+
+/// Finds the most pressure `agents` starting at `start` can relieve within `minutes`.
+/// Searches the routes of one agent depth-first. Only a single agent (part one) is pruned with an upper bound,
+/// see [Search::bound]. With several agents (part two) every route is visited, and [combine] assigns the best
+/// route of each set of valves to the agents.
+pub fn solve<'a>(
+    map: &'a HashMap<&str, Valve>,
+    start: &'a str,
+    agents: usize,
+    minutes: u32,
+) -> Plan<'a> {
+    let graph = Graph::new(map, start);
+    let mut search = Search {
+        graph: &graph,
+        minutes,
+        prune: agents == 1,
+        best: HashMap::new(),
+        best_total: 0,
+        route: vec![],
+    };
+    search.visit(graph.start, 0, 0, 0);
+
+    let mut entries: Vec<(u64, u32)> = search
+        .best
+        .iter()
+        .map(|(&mask, &(relieved, _))| (mask, relieved))
+        .collect();
+    entries.sort_by_key(|&(mask, relieved)| (std::cmp::Reverse(relieved), mask));
+
+    let mut best = (0, vec![]);
+    combine(&entries, agents, 0, 0, 0, &mut vec![], &mut best);
+
+    let (graph, routes) = (&graph, &search.best);
+    let mut schedule: Vec<Step> = best
+        .1
+        .iter()
+        .enumerate()
+        .flat_map(|(agent, &idx)| {
+            let (_, route) = &routes[&entries[idx].0];
+            route.iter().map(move |&(minute, valve)| Step {
+                minute,
+                agent,
+                valve: graph.names[valve],
+                flow: graph.flows[valve],
+                released: graph.flows[valve] * (minutes - minute),
+            })
+        })
+        .collect();
+    schedule.sort_by_key(|step| (step.minute, step.agent));
+
+    Plan {
+        relieved: best.0,
+        schedule,
+    }
+}
+
+const AGENT_COLORS: [&str; 4] = ["red", "blue", "darkgreen", "purple"];
+
+/// Renders the tunnels and the distances between valves with flow as Graphviz DOT, one graph each.
+/// Valves opened by `plan` are filled, and the route of each agent is drawn in the distance graph.
+pub fn to_dot(map: &HashMap<&str, Valve>, start: &str, plan: &Plan) -> String {
+    let opened: HashSet<&str> = plan.schedule.iter().map(|step| step.valve).collect();
+    let node = |name: &str| {
+        let mut attrs = format!("label=\"{}\\n{}\"", name, map[name].flow);
+        if name == start {
+            attrs.push_str(", shape=doublecircle");
+        }
+        if opened.contains(name) {
+            attrs.push_str(", style=filled, fillcolor=gold");
+        }
+        format!("    {} [{}];\n", name, attrs)
+    };
+
+    let mut out = String::from("graph tunnels {\n    node [shape=circle];\n");
+    for name in map.keys().sorted() {
+        out.push_str(&node(name));
+    }
+    for (name, valve) in map.iter().sorted_by_key(|(name, _)| **name) {
+        for neighbour in valve.neighbours.iter().filter(|n| *name < **n).sorted() {
+            out.push_str(&format!("    {} -- {};\n", name, neighbour));
+        }
+    }
+    out.push_str("}\n\n");
+
+    let dist_map = min_distance(map, start);
+    let distances: BTreeSet<(&str, &str, u32)> = dist_map
+        .iter()
+        .filter(|((from, to), _)| from != to)
+        .map(|(&(from, to), &dist)| (from.min(to), from.max(to), dist))
+        .collect();
+
+    out.push_str("digraph distances {\n    node [shape=circle];\n");
+    for name in map
+        .iter()
+        .filter(|(name, valve)| valve.flow > 0 || **name == start)
+        .map(|(name, _)| *name)
+        .sorted()
+    {
+        out.push_str(&node(name));
+    }
+    out.push_str("    edge [dir=none, color=gray, fontcolor=gray];\n");
+    for (from, to, dist) in distances {
+        out.push_str(&format!("    {} -> {} [label={}];\n", from, to, dist));
+    }
+
+    let agents = plan.schedule.iter().map(|step| step.agent).max();
+    out.push_str("    edge [dir=forward, penwidth=2];\n");
+    for agent in agents.into_iter().flat_map(|max| 0..=max) {
+        let color = AGENT_COLORS[agent % AGENT_COLORS.len()];
+        let route = plan.schedule.iter().filter(|step| step.agent == agent);
+        let mut from = start;
+        for step in route {
+            out.push_str(&format!(
+                "    {} -> {} [color={}, fontcolor={}, label=\"minute {}\"];\n",
+                from, step.valve, color, color, step.minute
+            ));
+            from = step.valve;
+        }
+    }
+    out.push_str("}\n");
+
+    out
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let map = parse(input);
+    Some(solve(&map, "AA", 1, 30).relieved)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let map = parse(input);
+    Some(solve(&map, "AA", 2, 26).relieved)
 }
 
 fn main() {
+    let mut args = pico_args::Arguments::from_env();
+    let timeline = args.contains("--timeline");
+    let bench = args.contains("--bench");
+    let dot: Option<String> = match args.opt_value_from_str("--dot") {
+        Ok(dot) => dot,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     let input = &advent_of_code::read_file("inputs", 16);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
+
+    // `cargo solve 16 -- --timeline` prints the schedules behind the answers.
+    if timeline {
+        let map = parse(input);
+        println!("Part 1:\n{}\n", solve(&map, "AA", 1, 30));
+        println!("Part 2:\n{}", solve(&map, "AA", 2, 26));
+    }
+
+    // `cargo solve 16 -- --dot valves.dot` writes the graph with the route of part one,
+    // render it with e.g. `dot -Tsvg -O valves.dot`.
+    if let Some(path) = dot {
+        let map = parse(input);
+        let plan = solve(&map, "AA", 1, 30);
+        match std::fs::write(&path, to_dot(&map, "AA", &plan)) {
+            Ok(_) => println!("Wrote graph to \"{}\".", path),
+            Err(e) => {
+                eprintln!("Failed to write \"{}\": {}", path, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // `cargo solve 16 --release -- --bench` compares the search with the breadth-first reference.
+    if bench {
+        let map = parse(input);
+        for (part, agents, minutes) in [(1, 1, 30), (2, 2, 26)] {
+            let timer = Instant::now();
+            let relieved = solve(&map, "AA", agents, minutes).relieved;
+            let elapsed = timer.elapsed();
+
+            let timer = Instant::now();
+            let reference = solve_bfs(&map, "AA", agents, minutes).relieved;
+            let reference_elapsed = timer.elapsed();
+
+            println!(
+                "Part {}: depth-first {} in {:.2?}, bfs {} in {:.2?}",
+                part, relieved, elapsed, reference, reference_elapsed
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -276,4 +679,119 @@ mod tests {
         let input = advent_of_code::read_file("examples", 16);
         assert_eq!(part_two(&input), Some(1707));
     }
+
+    #[test]
+    fn test_solve() {
+        let input = advent_of_code::read_file("examples", 16);
+        let map = parse(&input);
+
+        let plan = solve(&map, "AA", 2, 26);
+        assert!(plan.opened(0).is_disjoint(&plan.opened(1)));
+        assert_eq!(plan.opened(0).len() + plan.opened(1).len(), 6);
+
+        assert_eq!(solve(&map, "AA", 0, 30).relieved, 0);
+        assert_eq!(solve(&map, "AA", 3, 26).relieved, 1794);
+        assert_eq!(solve(&map, "AA", 1, 40).relieved, 2461);
+        assert_eq!(solve(&map, "JJ", 1, 30).relieved, 1807);
+    }
+
+    #[test]
+    fn test_solve_matches_bfs() {
+        let input = advent_of_code::read_file("examples", 16);
+        let map = parse(&input);
+
+        for (start, agents, minutes) in [
+            ("AA", 1, 30),
+            ("AA", 2, 26),
+            ("AA", 3, 26),
+            ("AA", 1, 40),
+            ("JJ", 1, 30),
+            ("JJ", 2, 10),
+            ("AA", 1, 2),
+        ] {
+            let plan = solve(&map, start, agents, minutes);
+            assert_eq!(
+                plan.relieved,
+                solve_bfs(&map, start, agents, minutes).relieved
+            );
+            assert_eq!(
+                plan.schedule.iter().map(|s| s.released).sum::<u32>(),
+                plan.relieved
+            );
+        }
+    }
+
+    #[test]
+    fn test_graph_mask_size() {
+        let names: Vec<String> = (0..64).map(|idx| format!("V{:02}", idx)).collect();
+        let valve = |flow| Valve {
+            flow,
+            neighbours: HashSet::new(),
+        };
+
+        let mut map: HashMap<&str, Valve> =
+            names[..63].iter().map(|n| (n.as_str(), valve(1))).collect();
+        map.insert("AA", valve(0));
+        assert_eq!(Graph::new(&map, "AA").names.len(), 64);
+
+        map.insert(names[63].as_str(), valve(1));
+        let too_many = std::panic::catch_unwind(|| Graph::new(&map, "AA").names.len());
+        assert!(too_many.is_err());
+        // a start with flow is already part of the mask.
+        assert_eq!(Graph::new(&map, "V00").names.len(), 64);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let input = advent_of_code::read_file("examples", 16);
+        let map = parse(&input);
+        let dot = to_dot(&map, "AA", &solve(&map, "AA", 2, 26));
+
+        let (tunnels, distances) = dot.split_once("\n\n").unwrap();
+        assert!(tunnels.starts_with("graph tunnels {"));
+        assert!(tunnels.contains("    AA [label=\"AA\\n0\", shape=doublecircle];"));
+        assert!(tunnels.contains("    DD [label=\"DD\\n20\", style=filled, fillcolor=gold];"));
+        assert!(tunnels.contains("    FF [label=\"FF\\n0\"];"));
+        assert!(tunnels.contains("    AA -- BB;"));
+        assert!(!tunnels.contains("    BB -- AA;"));
+
+        assert!(distances.starts_with("digraph distances {"));
+        assert!(!distances.contains("FF ["));
+        assert!(distances.contains("    BB -> HH [label=6];"));
+        assert!(distances.contains("    AA -> DD [color=red, fontcolor=red, label=\"minute 2\"];"));
+        assert!(
+            distances.contains("    AA -> JJ [color=blue, fontcolor=blue, label=\"minute 3\"];")
+        );
+    }
+
+    #[test]
+    fn test_schedule() {
+        let input = advent_of_code::read_file("examples", 16);
+        let map = parse(&input);
+        let plan = solve(&map, "AA", 1, 30);
+
+        let schedule: Vec<_> = plan.schedule.iter().map(|s| (s.minute, s.valve)).collect();
+        assert_eq!(
+            schedule,
+            vec![
+                (2, "DD"),
+                (5, "BB"),
+                (9, "JJ"),
+                (17, "HH"),
+                (21, "EE"),
+                (24, "CC")
+            ]
+        );
+        assert_eq!(plan.schedule.iter().map(|s| s.released).sum::<u32>(), 1651);
+        assert!(plan
+            .to_string()
+            .lines()
+            .nth(1)
+            .unwrap()
+            .ends_with("    DD    20       560"));
+
+        let plan = solve(&map, "AA", 2, 26);
+        assert_eq!(plan.schedule.iter().map(|s| s.released).sum::<u32>(), 1707);
+        assert!(plan.schedule.windows(2).all(|w| w[0].minute <= w[1].minute));
+    }
 }
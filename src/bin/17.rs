@@ -5,203 +5,448 @@ pub enum Jet {
     Right,
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Coord {
     x: usize,
     y: usize,
 }
 
-const WIDTH: usize = 7;
-const PIECES: [&[Coord]; 5] = [
-    // hozizontal line
-    &[
-        Coord { x: 0, y: 0 },
-        Coord { x: 1, y: 0 },
-        Coord { x: 2, y: 0 },
-        Coord { x: 3, y: 0 },
-    ],
-    // plus
-    &[
-        Coord { x: 0, y: 1 },
-        Coord { x: 1, y: 0 },
-        Coord { x: 1, y: 1 },
-        Coord { x: 1, y: 2 },
-        Coord { x: 2, y: 1 },
-    ],
-    // J (or backwards L)
-    &[
-        Coord { x: 0, y: 0 },
-        Coord { x: 1, y: 0 },
-        Coord { x: 2, y: 0 },
-        Coord { x: 2, y: 1 },
-        Coord { x: 2, y: 2 },
-    ],
-    // vertical line
-    &[
-        Coord { x: 0, y: 0 },
-        Coord { x: 0, y: 1 },
-        Coord { x: 0, y: 2 },
-        Coord { x: 0, y: 3 },
-    ],
-    // square
-    &[
-        Coord { x: 0, y: 0 },
-        Coord { x: 1, y: 0 },
-        Coord { x: 0, y: 1 },
-        Coord { x: 1, y: 1 },
-    ],
-];
-
-#[derive(Default)]
-pub struct State {
+/// Rows are stored as bit masks, so a chamber is at most this wide.
+const MAX_WIDTH: usize = u8::BITS as usize;
+
+/// The five rocks from the puzzle, in falling order.
+const ROCKS: &str = "\
+####
+
+.#.
+###
+.#.
+
+..#
+..#
+###
+
+#
+#
+#
+#
+
+##
+##";
+
+/// A rock as bit masks of its rows, bottom row first. Bit `x` is set for rock in column `x`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    rows: Vec<u8>,
+    width: usize,
+}
+
+impl Shape {
+    /// Parses a shape drawn with `#` for rock and `.` for air, top row first.
+    pub fn from_ascii(art: &str) -> Result<Shape, String> {
+        let rows: Vec<&str> = art.lines().map(str::trim_end).collect();
+        let mut cells = Vec::new();
+        for (row, line) in rows.iter().enumerate() {
+            for (x, char) in line.chars().enumerate() {
+                match char {
+                    '#' => cells.push(Coord {
+                        x,
+                        y: rows.len() - 1 - row,
+                    }),
+                    '.' => {}
+                    _ => return Err(format!("invalid character in shape: {}", char)),
+                }
+            }
+        }
+
+        // normalise so that the shape touches x = 0 and y = 0.
+        let min_x = cells.iter().map(|c| c.x).min().ok_or("empty shape")?;
+        let min_y = cells.iter().map(|c| c.y).min().ok_or("empty shape")?;
+        cells.iter_mut().for_each(|c| {
+            c.x -= min_x;
+            c.y -= min_y;
+        });
+        let width = cells.iter().map(|c| c.x + 1).max().unwrap_or(0);
+        if width > MAX_WIDTH {
+            return Err(format!("shapes are at most {} wide", MAX_WIDTH));
+        }
+
+        let height = cells.iter().map(|c| c.y + 1).max().unwrap_or(0);
+        let mut masks = vec![0; height];
+        cells.iter().for_each(|c| masks[c.y] |= 1 << c.x);
+        Ok(Shape { rows: masks, width })
+    }
+}
+
+/// Layout of the chamber and the rocks falling into it.
+pub struct Chamber {
+    width: usize,
+    shapes: Vec<Shape>,
+    /// Where a rock appears, relative to the left wall and the top of the tower.
+    spawn: Coord,
+    /// Characters for jets pushing left and right.
+    alphabet: [char; 2],
+}
+
+impl Default for Chamber {
+    fn default() -> Self {
+        Chamber::new(
+            7,
+            Chamber::parse_shapes(ROCKS).unwrap(),
+            Coord { x: 2, y: 3 },
+        )
+        .unwrap()
+    }
+}
+
+impl Chamber {
+    pub fn new(width: usize, shapes: Vec<Shape>, spawn: Coord) -> Result<Chamber, String> {
+        if shapes.is_empty() {
+            return Err("no shapes".to_string());
+        }
+        if width > MAX_WIDTH {
+            return Err(format!("the chamber is at most {} wide", MAX_WIDTH));
+        }
+        if let Some(shape) = shapes.iter().find(|s| spawn.x + s.width > width) {
+            return Err(format!(
+                "a shape of width {} does not fit at x={} in a chamber of width {}",
+                shape.width, spawn.x, width
+            ));
+        }
+        Ok(Chamber {
+            width,
+            shapes,
+            spawn,
+            alphabet: ['<', '>'],
+        })
+    }
+
+    pub fn with_alphabet(self, left: char, right: char) -> Chamber {
+        Chamber {
+            alphabet: [left, right],
+            ..self
+        }
+    }
+
+    /// Parses shapes drawn in ASCII art and separated by blank lines.
+    pub fn parse_shapes(art: &str) -> Result<Vec<Shape>, String> {
+        art.split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .map(Shape::from_ascii)
+            .collect()
+    }
+
+    pub fn parse_jets(&self, input: &str) -> Result<Vec<Jet>, String> {
+        let jets: Vec<Jet> = input
+            .trim()
+            .chars()
+            .map(|char| match char {
+                c if c == self.alphabet[0] => Ok(Jet::Left),
+                c if c == self.alphabet[1] => Ok(Jet::Right),
+                _ => Err(format!("invalid input : {}", char)),
+            })
+            .collect::<Result<_, _>>()?;
+        if jets.is_empty() {
+            return Err("no jets".to_string());
+        }
+        Ok(jets)
+    }
+
+    /// Height of the tower after `pieces` rocks have come to rest.
+    pub fn height(&self, jets: &[Jet], pieces: usize) -> usize {
+        State::new(self).simulate(pieces, jets)
+    }
+
+    /// Like `height`, but drops every rock instead of skipping over cycles.
+    pub fn simulated_height(&self, jets: &[Jet], pieces: usize) -> usize {
+        State {
+            skip_cycles: false,
+            ..State::new(self)
+        }
+        .simulate(pieces, jets)
+    }
+
+    /// Mask with a bit set for every column.
+    fn full_row(&self) -> u8 {
+        ((1u16 << self.width) - 1) as u8
+    }
+}
+
+/// Don't look for rows to prune until the tower has grown this tall.
+const PRUNE_AT: usize = 64;
+
+/// How many more times a cycle has to repeat with the same growth before skipping ahead.
+const VERIFY_ROUNDS: usize = 2;
+
+/// Next piece, next jet and the surface profile of the tower.
+type CycleKey = (usize, usize, Vec<usize>);
+
+/// A repeating state whose height growth is being confirmed.
+struct Cycle {
+    length: usize,
+    delta: usize,
+    rounds: usize,
+    /// Piece count at which the next round ends, and the top when the current one started.
+    at: usize,
+    top: usize,
+}
+
+pub struct State<'a> {
+    chamber: &'a Chamber,
     jet_count: usize,
     piece_count: usize,
-    top: usize,
-    map: Vec<[bool; WIDTH]>,
-    curr: Coord,
-    seen: HashMap<(usize, usize), (usize, usize, usize)>,
+    /// Rows of the tower above `pruned`, as bit masks. The top row always contains rock.
+    rows: Vec<u8>,
+    /// Number of rows dropped from the bottom because no rock can reach them anymore.
+    pruned: usize,
+    prune_at: usize,
+    /// The falling rock, shifted to its column, and the row of its bottom.
+    piece: Vec<u8>,
+    y: usize,
+    skip_cycles: bool,
+    verify_rounds: usize,
+    /// Piece count and top at which each key was last seen.
+    seen: HashMap<CycleKey, (usize, usize)>,
+    cycle: Option<Cycle>,
     added_by_repeat: usize,
 }
 
-impl State {
-    pub fn is_valid(&mut self, new_curr: &Coord, piece: &[Coord]) -> bool {
-        piece.iter().all(|offset| {
-            let x = new_curr.x + offset.x;
-            let y = new_curr.y + offset.y;
-            while self.map.len() <= y {
-                self.map.push([false; WIDTH]);
-            }
-            x < WIDTH && !self.map[y][x]
-        })
+impl<'a> State<'a> {
+    pub fn new(chamber: &'a Chamber) -> Self {
+        State {
+            chamber,
+            jet_count: 0,
+            piece_count: 0,
+            rows: Vec::new(),
+            pruned: 0,
+            prune_at: PRUNE_AT,
+            piece: Vec::new(),
+            y: 0,
+            skip_cycles: true,
+            verify_rounds: VERIFY_ROUNDS,
+            seen: HashMap::new(),
+            cycle: None,
+            added_by_repeat: 0,
+        }
     }
 
-    pub fn simulate(&mut self, target: usize, jets: Vec<Jet>) -> Option<usize> {
-        while self.piece_count < target {
-            let piece = PIECES[self.piece_count % PIECES.len()];
+    pub fn top(&self) -> usize {
+        self.pruned + self.rows.len()
+    }
 
-            self.curr.x = 2;
-            self.curr.y = self.top + 3;
+    /// Whether `piece` overlaps rock with its bottom at row `y`. Pruned rows count as rock.
+    pub fn collides(&self, piece: &[u8], y: usize) -> bool {
+        piece.iter().enumerate().any(|(dy, mask)| {
+            let y = y + dy;
+            y < self.pruned
+                || self
+                    .rows
+                    .get(y - self.pruned)
+                    .is_some_and(|row| row & mask != 0)
+        })
+    }
 
-            loop {
-                let jet = &jets[self.jet_count % jets.len()];
-                let new_curr = match jet {
-                    Jet::Left => Coord {
-                        x: self.curr.x.saturating_sub(1),
-                        y: self.curr.y,
-                    },
-                    Jet::Right => Coord {
-                        x: self.curr.x + 1,
-                        y: self.curr.y,
-                    },
-                };
-                if self.is_valid(&new_curr, piece) {
-                    self.curr = new_curr;
-                }
-                self.jet_count += 1;
+    /// The falling rock pushed by `jet`, unless a wall is in the way.
+    fn pushed(&self, jet: &Jet) -> Option<Vec<u8>> {
+        match jet {
+            Jet::Left => self
+                .piece
+                .iter()
+                .all(|row| row & 1 == 0)
+                .then(|| self.piece.iter().map(|row| row >> 1).collect()),
+            Jet::Right => {
+                let wall = 1 << (self.chamber.width - 1);
+                self.piece
+                    .iter()
+                    .all(|row| row & wall == 0)
+                    .then(|| self.piece.iter().map(|row| row << 1).collect())
+            }
+        }
+    }
 
-                let new_curr = Coord {
-                    x: self.curr.x,
-                    y: self.curr.y.saturating_sub(1),
-                };
+    /// Drops the rows below the lowest air that a rock falling from the top can reach.
+    fn prune(&mut self) {
+        if self.rows.len() < self.prune_at {
+            return;
+        }
 
-                if self.curr.y == 0 || !self.is_valid(&new_curr, piece) {
+        let full = self.chamber.full_row();
+        let mut reached = full;
+        let mut lowest = self.rows.len();
+        for y in (0..self.rows.len()).rev() {
+            let air = !self.rows[y] & full;
+            // air entered from above, spread sideways through the row.
+            let mut flood = reached & air;
+            loop {
+                let next = (flood | flood << 1 | flood >> 1) & air;
+                if next == flood {
                     break;
                 }
-                self.curr = new_curr
+                flood = next;
             }
-
-            for offset in piece {
-                let x = self.curr.x + offset.x;
-                let y = self.curr.y + offset.y;
-                while self.map.len() <= y {
-                    self.map.push([false; WIDTH]);
-                }
-                self.map[y][x] = true;
-                self.top = self.top.max(y + 1);
+            if flood == 0 {
+                break;
             }
+            reached = flood;
+            lowest = y;
+        }
 
-            if self.added_by_repeat == 0 {
-                let key = (self.piece_count % PIECES.len(), self.jet_count % jets.len());
+        self.rows.drain(..lowest);
+        self.pruned += lowest;
+        // towers with an open shaft would otherwise be flooded after every rock.
+        self.prune_at = PRUNE_AT.max(2 * self.rows.len());
+    }
 
-                if let Some((2, old_piece_count, old_top)) = self.seen.get(&key) {
-                    let delta_top = self.top - old_top;
-                    let delta_piece_count = self.piece_count - old_piece_count;
-                    let repeats = (target - self.piece_count) / delta_piece_count;
-                    self.piece_count += repeats * delta_piece_count;
-                    self.added_by_repeat = delta_top * repeats;
+    /// Drops the next rock until it comes to rest.
+    fn drop_piece(&mut self, jets: &[Jet]) {
+        let chamber = self.chamber;
+        let shapes = &chamber.shapes;
+        let shape = &shapes[self.piece_count % shapes.len()];
+        self.piece = shape
+            .rows
+            .iter()
+            .map(|row| row << chamber.spawn.x)
+            .collect();
+        self.y = self.top() + chamber.spawn.y;
+
+        loop {
+            let jet = &jets[self.jet_count % jets.len()];
+            if let Some(pushed) = self.pushed(jet) {
+                if !self.collides(&pushed, self.y) {
+                    self.piece = pushed;
                 }
+            }
+            self.jet_count += 1;
 
-                self
-                .seen
-                .entry(key)
-                    .and_modify(|(amnt, old_piece_count, old_top)| {
-                        *amnt += 1;
-                        *old_piece_count = self.piece_count;
-                        *old_top = self.top;
-                    })
-                .or_insert((1, self.piece_count, self.top));
+            if self.y == 0 || self.collides(&self.piece, self.y - 1) {
+                break;
             }
+            self.y -= 1;
+        }
 
-            self.piece_count += 1;
+        for (dy, mask) in self.piece.iter().enumerate() {
+            let row = self.y + dy - self.pruned;
+            if self.rows.len() <= row {
+                self.rows.resize(row + 1, 0);
+            }
+            self.rows[row] |= mask;
         }
-        Some(self.top + self.added_by_repeat)
+        self.prune();
+        self.piece_count += 1;
     }
-}
 
-impl Display for State {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let piece = PIECES[self.piece_count % PIECES.len()];
-        let mut print: Vec<Vec<_>> = self
-            .map
-            .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|rock| if *rock { '#' } else { '.' })
-                    .collect()
+    /// Depth of the highest rock in each column below the top. Pruned rows count as rock.
+    pub fn profile(&self) -> Vec<usize> {
+        (0..self.chamber.width)
+            .map(|x| {
+                self.rows
+                    .iter()
+                    .rev()
+                    .position(|row| row >> x & 1 == 1)
+                    .unwrap_or(self.rows.len())
             })
-            .collect();
-        let mut local_top = self.top + 1;
-        for offset in piece {
-            let x = self.curr.x + offset.x;
-            let y = self.curr.y + offset.y;
-            while print.len() <= y {
-                print.push(vec!['.'; WIDTH]);
+            .collect()
+    }
+
+    /// Looks for a repeating state and, once its growth is confirmed, skips as many cycles as fit.
+    fn track_cycle(&mut self, target: usize, jets: usize) {
+        let top = self.top();
+        match self.cycle.take() {
+            Some(cycle) if self.piece_count < cycle.at => self.cycle = Some(cycle),
+            // a cycle that grows differently is dropped and the search goes on.
+            Some(cycle) if top - cycle.top == cycle.delta => {
+                self.cycle = Some(Cycle {
+                    rounds: cycle.rounds - 1,
+                    at: cycle.at + cycle.length,
+                    top,
+                    ..cycle
+                })
+            }
+            Some(_) => {}
+            None => {
+                let shapes = self.chamber.shapes.len();
+                let key = (
+                    self.piece_count % shapes,
+                    self.jet_count % jets,
+                    self.profile(),
+                );
+                if let Some((piece_count, old_top)) = self.seen.insert(key, (self.piece_count, top))
+                {
+                    let length = self.piece_count - piece_count;
+                    self.cycle = Some(Cycle {
+                        length,
+                        delta: top - old_top,
+                        rounds: self.verify_rounds,
+                        at: self.piece_count + length,
+                        top,
+                    });
+                }
             }
-            print[y][x] = '@';
-            local_top = local_top.max(y + 1);
         }
 
-        for row in (0..local_top).rev() {
-            let mut row_str = String::from('|');
-            for col in 0..WIDTH {
-                row_str.push(print[row][col]);
+        if let Some(Cycle {
+            length,
+            delta,
+            rounds: 0,
+            ..
+        }) = self.cycle
+        {
+            let repeats = (target - self.piece_count) / length;
+            self.piece_count += repeats * length;
+            self.added_by_repeat = repeats * delta;
+            self.cycle = None;
+        }
+    }
+
+    pub fn simulate(&mut self, target: usize, jets: &[Jet]) -> usize {
+        while self.piece_count < target {
+            self.drop_piece(jets);
+            if self.skip_cycles && self.added_by_repeat == 0 {
+                self.track_cycle(target, jets.len());
             }
-            row_str.push('|');
-            row_str.push('\n');
-            write!(f, "{}", row_str)?;
         }
-        writeln!(f, "+{}+", "-".repeat(WIDTH))
+        self.top() + self.added_by_repeat
+    }
+}
+
+impl Display for State<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self.chamber.width;
+        let top = self.top().max(self.y + self.piece.len());
+        for y in (self.pruned..top).rev() {
+            let rock = self.rows.get(y - self.pruned).copied().unwrap_or(0);
+            let falling = y
+                .checked_sub(self.y)
+                .and_then(|dy| self.piece.get(dy))
+                .copied()
+                .unwrap_or(0);
+            let row: String = (0..width)
+                .map(|x| match (falling >> x & 1, rock >> x & 1) {
+                    (1, _) => '@',
+                    (_, 1) => '#',
+                    _ => '.',
+                })
+                .collect();
+            writeln!(f, "|{}|", row)?;
+        }
+        // pruned rows are not shown.
+        let floor = if self.pruned == 0 { "-" } else { "~" };
+        writeln!(f, "+{}+", floor.repeat(width))
     }
 }
 
 pub fn parse(input: &str) -> Vec<Jet> {
-    input
-        .trim()
-        .chars()
-        .map(|char| match char {
-            '>' => Jet::Right,
-            '<' => Jet::Left,
-            _ => panic!("invalid input : {}", char),
-        })
-        .collect()
+    Chamber::default()
+        .parse_jets(input)
+        .unwrap_or_else(|e| panic!("{}", e))
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    State::default().simulate(2022, parse(input))
+    Some(Chamber::default().height(&parse(input), 2022))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    State::default().simulate(1_000_000_000_000, parse(input))
+    Some(Chamber::default().height(&parse(input), 1_000_000_000_000))
 }
 
 fn main() {
@@ -225,4 +470,144 @@ mod tests {
         let input = advent_of_code::read_file("examples", 17);
         assert_eq!(part_two(&input), Some(1514285714288));
     }
+
+    #[test]
+    fn test_heights() {
+        let input = advent_of_code::read_file("examples", 17);
+        let jets = parse(&input);
+        let chamber = Chamber::default();
+        assert_eq!(chamber.height(&jets, 0), 0);
+        assert_eq!(chamber.height(&jets, 1), 1);
+        assert_eq!(chamber.height(&jets, 2), 4);
+        assert_eq!(chamber.height(&jets, 10), 17);
+    }
+
+    #[test]
+    fn test_from_ascii() {
+        let plus = Shape::from_ascii(".#.\n###\n.#.").unwrap();
+        assert_eq!(plus.width, 3);
+        assert_eq!(plus.rows, vec![0b010, 0b111, 0b010]);
+        let j = Shape::from_ascii("..#\n..#\n###").unwrap();
+        assert_eq!(j.rows, vec![0b111, 0b100, 0b100]);
+
+        // empty rows and columns around the rock are dropped.
+        let dot = Shape::from_ascii("...\n.#.\n...").unwrap();
+        assert_eq!(dot.rows, vec![1]);
+
+        assert!(Shape::from_ascii("...").is_err());
+        assert!(Shape::from_ascii("#x").is_err());
+        assert!(Shape::from_ascii("#########").is_err());
+    }
+
+    #[test]
+    fn test_custom_chamber() {
+        // a rock as wide as the chamber always completes a single row.
+        let row = Chamber::parse_shapes("####").unwrap();
+        let chamber = Chamber::new(4, row, Coord { x: 0, y: 3 }).unwrap();
+        let jets = chamber.parse_jets("<>").unwrap();
+        assert_eq!(chamber.height(&jets, 100), 100);
+        assert_eq!(chamber.height(&jets, 1_000_000_000_000), 1_000_000_000_000);
+
+        // single cells pushed to the right stack up in the last column.
+        let cell = Chamber::parse_shapes("#").unwrap();
+        let chamber = Chamber::new(3, cell, Coord { x: 0, y: 1 })
+            .unwrap()
+            .with_alphabet('L', 'R');
+        let jets = chamber.parse_jets("R").unwrap();
+        assert_eq!(chamber.height(&jets, 7), 7);
+        assert!(chamber.parse_jets(">").is_err());
+
+        let wide = Chamber::parse_shapes("###").unwrap();
+        assert!(Chamber::new(4, wide, Coord { x: 2, y: 3 }).is_err());
+    }
+
+    #[test]
+    fn test_simulated_matches_cycles() {
+        let input = advent_of_code::read_file("examples", 17);
+        let jets = parse(&input);
+        let chamber = Chamber::default();
+        for pieces in [2022, 10_000, 123_457] {
+            assert_eq!(
+                chamber.height(&jets, pieces),
+                chamber.simulated_height(&jets, pieces)
+            );
+        }
+    }
+
+    #[test]
+    fn test_prune() {
+        let input = advent_of_code::read_file("examples", 17);
+        let jets = parse(&input);
+        let chamber = Chamber::default();
+        let mut state = State {
+            skip_cycles: false,
+            ..State::new(&chamber)
+        };
+        assert_eq!(state.simulate(100_000, &jets), 151_434);
+        assert!(state.rows.len() < 2 * PRUNE_AT);
+
+        // the wall column stays open, so nothing can be pruned.
+        let cell = Chamber::parse_shapes("#").unwrap();
+        let chamber = Chamber::new(2, cell, Coord { x: 0, y: 1 }).unwrap();
+        let jets = chamber.parse_jets("<").unwrap();
+        let mut state = State::new(&chamber);
+        assert_eq!(state.simulate(500, &jets), 500);
+        assert_eq!(state.pruned, 0);
+    }
+
+    #[test]
+    fn test_profile() {
+        let shapes = Chamber::parse_shapes("#..\n###").unwrap();
+        let chamber = Chamber::new(4, shapes, Coord { x: 0, y: 3 }).unwrap();
+        let jets = chamber.parse_jets("<").unwrap();
+        let mut state = State::new(&chamber);
+        state.simulate(1, &jets);
+        assert_eq!(state.profile(), vec![0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_custom_cycles() {
+        let shapes = Chamber::parse_shapes("##\n#.\n\n###\n\n#\n#").unwrap();
+        let chamber = Chamber::new(5, shapes, Coord { x: 1, y: 2 }).unwrap();
+        let jets = chamber.parse_jets("<<>><>>><").unwrap();
+        for pieces in [1000, 1001, 1002, 4321] {
+            assert_eq!(
+                chamber.height(&jets, pieces),
+                chamber.simulated_height(&jets, pieces)
+            );
+        }
+    }
+
+    #[test]
+    fn test_unverified_cycles() {
+        let input = advent_of_code::read_file("examples", 17);
+        let jets = parse(&input);
+        let chamber = Chamber::default();
+        let mut state = State {
+            verify_rounds: 0,
+            ..State::new(&chamber)
+        };
+        assert_eq!(state.simulate(1_000_000_000_000, &jets), 1514285714288);
+    }
+
+    #[test]
+    fn test_false_cycles_are_rejected() {
+        // the surface profile repeats before the tower does, it does not see the air under overhangs.
+        let shapes = Chamber::parse_shapes("##\n#.\n\n#").unwrap();
+        let chamber = Chamber::new(5, shapes, Coord { x: 2, y: 1 }).unwrap();
+        let jets = chamber.parse_jets("<<<<>>>><<>").unwrap();
+        let expected = chamber.simulated_height(&jets, 100_000);
+
+        let mut unverified = State {
+            verify_rounds: 0,
+            ..State::new(&chamber)
+        };
+        assert_ne!(unverified.simulate(100_000, &jets), expected);
+
+        let mut verified = State::new(&chamber);
+        assert_eq!(verified.simulate(100_000, &jets), expected);
+        // the false cycle was dropped and the search went on before skipping ahead.
+        assert!(verified.added_by_repeat > 0);
+        assert!(verified.seen.len() > unverified.seen.len());
+    }
 }
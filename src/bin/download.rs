@@ -3,6 +3,8 @@
  * There is no need to edit this file unless you want to change template functionality.
  */
 use advent_of_code::aoc_cli;
+use advent_of_code::config;
+use advent_of_code::session;
 use std::process;
 
 struct Args {
@@ -13,12 +15,14 @@ struct Args {
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
-        day: args.free_from_str()?,
+        day: args.free_from_fn(advent_of_code::parse_day)?,
         year: args.opt_value_from_str(["-y", "--year"])?,
     })
 }
 
 fn main() {
+    config::require();
+
     let args = match parse_args() {
         Ok(args) => args,
         Err(e) => {
@@ -32,15 +36,13 @@ fn main() {
         process::exit(1);
     }
 
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
-            process::exit(1);
-        }
+    if let Err(e) = session::precheck() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+
+    if let Err(e) = aoc_cli::download(args.day, args.year) {
+        eprintln!("failed to download puzzle: {}", e);
+        process::exit(1);
     }
 }
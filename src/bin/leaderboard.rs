@@ -0,0 +1,75 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{config, leaderboard, template};
+use std::{fs, process};
+
+struct Args {
+    id: Option<u64>,
+    year: u16,
+    refresh: bool,
+    file: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args
+            .opt_value_from_str(["-y", "--year"])?
+            .or(config::get().year)
+            .unwrap_or_else(template::current_year),
+        refresh: args.contains(["-r", "--refresh"]),
+        file: args.opt_value_from_str(["-f", "--file"])?,
+        id: args.opt_free_from_str()?,
+    })
+}
+
+/// Leaderboard JSON from `--file`, the cache or the website, in that order.
+fn load(args: &Args) -> Result<String, String> {
+    if let Some(file) = &args.file {
+        return fs::read_to_string(file).map_err(|e| format!("could not read \"{}\": {}", file, e));
+    }
+
+    let id = args
+        .id
+        .ok_or("missing leaderboard id. Usage: cargo leaderboard <id>")?;
+
+    if !args.refresh {
+        if let Some(cached) = leaderboard::read_cache(args.year, id) {
+            return Ok(cached);
+        }
+    }
+
+    let json = leaderboard::fetch(args.year, id)?;
+    if let Err(e) = leaderboard::write_cache(args.year, id, &json) {
+        eprintln!("could not cache leaderboard: {}", e);
+    }
+    Ok(json)
+}
+
+fn main() {
+    config::require();
+
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let board = match load(&args).and_then(|json| leaderboard::parse(&json)) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    println!("🎄 Leaderboard {}", board.year);
+    println!();
+    print!("{}", leaderboard::render_standings(&board));
+    println!();
+    print!("{}", leaderboard::render_days(&board));
+}
@@ -2,23 +2,39 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::{aoc_cli, config, puzzle, session};
+use std::{env, fs, process};
+
+const DEFAULT_WIDTH: usize = 80;
 
 struct Args {
     day: u8,
     year: Option<u16>,
+    refresh: bool,
+    width: Option<usize>,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
-        day: args.free_from_str()?,
         year: args.opt_value_from_str(["-y", "--year"])?,
+        refresh: args.contains(["-r", "--refresh"]),
+        width: args.opt_value_from_str(["-w", "--width"])?,
+        day: args.free_from_fn(advent_of_code::parse_day)?,
     })
 }
 
+/// Wrap width: `--width`, then `$COLUMNS` if exported, then 80.
+/// Shells usually do not export `COLUMNS`, so pass `--width` to match the terminal.
+fn width(args: &Args) -> usize {
+    args.width
+        .or_else(|| env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
 fn main() {
+    config::require();
+
     let args = match parse_args() {
         Ok(args) => args,
         Err(e) => {
@@ -27,20 +43,26 @@ fn main() {
         }
     };
 
+    // stored puzzles belong to the current year. other years and `--refresh` are read from the website.
+    if !args.refresh && args.year.is_none() {
+        if let Ok(description) = fs::read_to_string(aoc_cli::get_puzzle_path(args.day)) {
+            print!("{}", puzzle::render(&description, width(&args)));
+            return;
+        }
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    match aoc_cli::read(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
-            process::exit(1);
-        }
+    if let Err(e) = session::precheck() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+
+    if let Err(e) = aoc_cli::read(args.day, args.year) {
+        eprintln!("failed to read puzzle: {}", e);
+        process::exit(1);
     }
 }
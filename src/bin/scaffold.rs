@@ -2,108 +2,329 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::template::{self, Placeholders};
+use advent_of_code::{aoc_cli, config, puzzle, registry, session};
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
+struct Args {
+    day: u8,
+    year: Option<u16>,
+    template: String,
+    answer_type: Option<String>,
+    title: Option<String>,
+    imports: Vec<String>,
+    download: bool,
+    undo: bool,
+    force: bool,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        template: args
+            .opt_value_from_str(["-t", "--template"])?
+            .unwrap_or_else(|| "plain".into()),
+        answer_type: args.opt_value_from_str(["-a", "--answer-type"])?,
+        title: args.opt_value_from_str("--title")?,
+        imports: args.values_from_str("--use")?,
+        download: args.contains(["-d", "--download"]),
+        undo: args.contains(["-u", "--undo"]),
+        force: args.contains(["-f", "--force"]),
+        day: args.free_from_fn(advent_of_code::parse_day)?,
+    })
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+fn create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Size of the file at `path` if it exists and is not empty.
+fn existing_size(path: &str) -> Option<u64> {
+    fs::metadata(path)
+        .ok()
+        .map(|m| m.len())
+        .filter(|len| *len > 0)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Removes the files of a scaffolded day and unregisters it.
+/// A module that changed since it was scaffolded and the input are only removed with `force`.
+/// Days registered without a fingerprint fall back to comparing the module with `rendered`.
+fn undo(day: u8, rendered: &[String], force: bool) {
+    let module_path = format!("src/bin/{:02}.rs", day);
+    let input_path = aoc_cli::get_input_path(day);
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
+    if let Ok(module) = fs::read_to_string(&module_path) {
+        let unchanged = match registry::scaffolded(day) {
+            Some(fingerprint) => registry::fingerprint(&module) == fingerprint,
+            None => rendered.contains(&module),
+        };
+        if !force && !unchanged {
+            eprintln!(
+                "\"{}\" changed since it was scaffolded and may contain a solution. Pass --force to remove it anyway.",
+                module_path
+            );
+            process::exit(1);
+        }
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+    let mut paths = vec![
+        module_path,
+        aoc_cli::get_example_path(day),
+        aoc_cli::get_puzzle_path(day),
+    ];
+    if force {
+        paths.push(input_path);
+    } else if fs::metadata(&input_path).is_ok() {
+        // inputs are not committed, so they could not be restored.
+        println!(
+            "Kept input file \"{}\". Pass --force to remove it as well.",
+            input_path
+        );
     }
-}
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+    let mut removed = false;
+    for path in paths.iter().filter(|p| fs::metadata(p).is_ok()) {
+        match fs::remove_file(path) {
+            Ok(_) => {
+                println!("Removed \"{}\"", path);
+                removed = true;
+            }
+            Err(e) => {
+                eprintln!("Failed to remove \"{}\": {}", path, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    match registry::remove(day) {
+        Ok(true) => println!("Removed day {} from \"{}\"", day, registry::REGISTRY_PATH),
+        Ok(false) if !removed => {
+            eprintln!("Day {} is not scaffolded.", day);
+            process::exit(1);
+        }
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to update \"{}\": {}", registry::REGISTRY_PATH, e);
+            process::exit(1);
+        }
+    }
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// Files touched by scaffold. If a step fails, they are restored to their previous contents or removed.
+#[derive(Default)]
+struct Transaction {
+    backups: Vec<(String, Option<Vec<u8>>)>,
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+impl Transaction {
+    fn track(&mut self, path: &str) {
+        if !self.backups.iter().any(|(p, _)| p == path) {
+            self.backups.push((path.to_string(), fs::read(path).ok()));
+        }
+    }
+
+    fn fail(&self, message: &str) -> ! {
+        eprintln!("{}", message);
+        for (path, contents) in self.backups.iter().rev() {
+            let restored = match contents {
+                Some(contents) => fs::write(path, contents),
+                None => fs::remove_file(path),
+            };
+            if restored.is_ok() {
+                eprintln!("Rolled back \"{}\"", path);
+            }
+        }
+        process::exit(1);
+    }
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
+    config::require();
+
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
             eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
             process::exit(1);
         }
     };
 
+    let day = args.day;
     let day_padded = format!("{:02}", day);
 
-    let input_path = format!("src/inputs/{}.txt", day_padded);
-    let example_path = format!("src/examples/{}.txt", day_padded);
+    let input_path = aoc_cli::get_input_path(day);
+    let example_path = aoc_cli::get_example_path(day);
     let module_path = format!("src/bin/{}.rs", day_padded);
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+    let module_template = template::load(&args.template);
+
+    let year = args
+        .year
+        .or(config::get().year)
+        .unwrap_or_else(template::current_year);
+    let mut placeholders = Placeholders::new(day, year);
+    if let Some(title) = &args.title {
+        placeholders.title = title.clone();
+    }
+    if let Some(answer_type) = args.answer_type {
+        placeholders.answer_type = answer_type;
+    }
+    placeholders.imports = args.imports;
+
+    if args.undo {
+        // without a recorded fingerprint, the module is unmodified if it matches the template rendered with the same flags.
+        let mut rendered = vec![];
+        if let Ok(module_template) = &module_template {
+            rendered.push(template::render(module_template, &placeholders));
+            // `--download` takes the title from the puzzle description.
+            let puzzle_title = fs::read_to_string(aoc_cli::get_puzzle_path(day))
+                .ok()
+                .and_then(|description| puzzle::parse_title(&description));
+            if let (None, Some(title)) = (&args.title, puzzle_title) {
+                placeholders.title = title;
+                rendered.push(template::render(module_template, &placeholders));
+            }
+        }
+        undo(day, &rendered, args.force);
+        return;
+    }
+
+    let module_template = match module_template {
+        Ok(module_template) => module_template,
         Err(e) => {
-            eprintln!("Failed to create module file: {}", e);
+            eprintln!("Failed to load template: {}", e);
             process::exit(1);
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
+    if fs::metadata(&module_path).is_ok() {
+        eprintln!(
+            "Failed to create module file: \"{}\" already exists.",
+            &module_path
+        );
+        process::exit(1);
+    }
+
+    let mut transaction = Transaction::default();
+    let mut example = None;
+
+    if args.download {
+        if aoc_cli::check().is_err() {
+            eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+            process::exit(1);
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {}", e);
+        if let Err(e) = session::precheck() {
+            eprintln!("{}", e);
             process::exit(1);
         }
-    }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+        let puzzle_path = aoc_cli::get_puzzle_path(day);
+        if let Some(size) = existing_size(&input_path) {
+            println!(
+                "Replacing existing input file \"{}\" ({} bytes) with the downloaded input.",
+                &input_path, size
+            );
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {}", e);
-            process::exit(1);
+        transaction.track(&input_path);
+        transaction.track(&puzzle_path);
+
+        if let Err(e) = aoc_cli::download(day, args.year) {
+            transaction.fail(&format!("Failed to download puzzle: {}", e));
         }
+
+        let description = match fs::read_to_string(&puzzle_path) {
+            Ok(description) => description,
+            Err(e) => transaction.fail(&format!("Failed to read puzzle description: {}", e)),
+        };
+
+        if args.title.is_none() {
+            if let Some(title) = puzzle::parse_title(&description) {
+                placeholders.title = title;
+            }
+        }
+
+        example = match puzzle::parse_example(&description) {
+            Some(example) => Some(example),
+            None => transaction.fail(&format!(
+                "Failed to find an example in \"{}\".",
+                &puzzle_path
+            )),
+        };
     }
 
-    match create_file(&example_path) {
+    transaction.track(&module_path);
+    let mut file = match safe_create_file(&module_path) {
+        Ok(file) => file,
+        Err(e) => transaction.fail(&format!("Failed to create module file: {}", e)),
+    };
+
+    let module = template::render(&module_template, &placeholders);
+    match file.write_all(module.as_bytes()) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {}", e);
-            process::exit(1);
+            println!("Created module file \"{}\"", &module_path);
         }
+        Err(e) => transaction.fail(&format!("Failed to write module contents: {}", e)),
+    }
+
+    transaction.track(&input_path);
+    match existing_size(&input_path) {
+        Some(_) if args.download => {}
+        Some(size) => println!(
+            "Found existing input file \"{}\" ({} bytes), keeping it.",
+            &input_path, size
+        ),
+        None => match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", &input_path);
+            }
+            Err(e) => transaction.fail(&format!("Failed to create input file: {}", e)),
+        },
+    }
+
+    transaction.track(&example_path);
+    match (existing_size(&example_path), example) {
+        (Some(size), _) => println!(
+            "Found existing example file \"{}\" ({} bytes), keeping it.",
+            &example_path, size
+        ),
+        (None, Some(example)) => match create_file(&example_path)
+            .and_then(|mut file| file.write_all(example.as_bytes()))
+        {
+            Ok(_) => {
+                println!("Wrote example from puzzle to \"{}\"", &example_path);
+            }
+            Err(e) => transaction.fail(&format!("Failed to write example file: {}", e)),
+        },
+        (None, None) => match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Err(e) => transaction.fail(&format!("Failed to create example file: {}", e)),
+        },
+    }
+
+    transaction.track(registry::REGISTRY_PATH);
+    match registry::add(day, &module) {
+        Ok(_) => println!("Registered day {} in \"{}\"", day, registry::REGISTRY_PATH),
+        Err(e) => transaction.fail(&format!("Failed to update day registry: {}", e)),
     }
 
     println!("---");
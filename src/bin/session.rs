@@ -0,0 +1,108 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::config;
+use advent_of_code::session::{self, SessionError};
+use std::{io, process, time::Duration};
+
+const USAGE: &str = "Usage: cargo session set [<token>] | show | check";
+
+enum Action {
+    Set(Option<String>),
+    Show,
+    Check,
+}
+
+fn parse_args() -> Result<Action, String> {
+    let mut args = pico_args::Arguments::from_env();
+    let action: String = args.free_from_str().map_err(|_| USAGE.to_string())?;
+    match action.as_str() {
+        "set" => Ok(Action::Set(
+            args.opt_free_from_str().map_err(|e| e.to_string())?,
+        )),
+        "show" => Ok(Action::Show),
+        "check" => Ok(Action::Check),
+        _ => Err(format!("unknown action \"{}\". {}", action, USAGE)),
+    }
+}
+
+fn days(duration: Duration) -> u64 {
+    duration.as_secs() / (24 * 60 * 60)
+}
+
+fn fail(e: SessionError) -> ! {
+    eprintln!("{}", e);
+    process::exit(1);
+}
+
+fn set(token: Option<String>) {
+    // reading from stdin keeps the token out of the shell history.
+    let token = token.unwrap_or_else(|| {
+        eprintln!("Paste the value of your `session` cookie and press enter:");
+        let mut line = String::new();
+        if let Err(e) = io::stdin().read_line(&mut line) {
+            eprintln!("Failed to read token: {}", e);
+            process::exit(1);
+        }
+        line
+    });
+
+    match session::store(&token) {
+        Ok(path) => {
+            println!("🎄 Stored session cookie in \"{}\".", path.display());
+            println!("Run `cargo session check` to verify it.");
+        }
+        Err(e) => fail(e),
+    }
+}
+
+fn show() {
+    let token = session::read().unwrap_or_else(|e| fail(e));
+
+    println!(
+        "Path:    {}",
+        session::path().unwrap_or_else(|e| fail(e)).display()
+    );
+    println!("Token:   {}", session::mask(&token));
+    if let Some(age) = session::age() {
+        println!("Stored:  {} day(s) ago", days(age));
+    }
+    if session::is_exposed() {
+        println!("⚠️  The session file is readable by other users. Run `cargo session set` to restrict it.");
+    }
+}
+
+fn check() {
+    let token = session::read().unwrap_or_else(|e| fail(e));
+
+    match session::check(&token) {
+        Ok(user) => println!("🎄 The session cookie is valid, logged in as {}.", user),
+        Err(e) => fail(e),
+    }
+
+    if let Some(age) = session::age().filter(|age| *age > session::MAX_AGE) {
+        println!(
+            "⚠️  The session cookie was stored {} days ago and will expire soon.",
+            days(age)
+        );
+    }
+}
+
+fn main() {
+    config::require();
+
+    let action = match parse_args() {
+        Ok(action) => action,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    match action {
+        Action::Set(token) => set(token),
+        Action::Show => show(),
+        Action::Check => check(),
+    }
+}
@@ -0,0 +1,135 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{answers, config, leaderboard, readme, template};
+use std::{collections::BTreeMap, fs, process};
+
+struct Args {
+    year: u16,
+    leaderboard: Option<String>,
+    user: Option<u64>,
+    dry_run: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args
+            .opt_value_from_str(["-y", "--year"])?
+            .or(config::get().year)
+            .unwrap_or_else(template::current_year),
+        leaderboard: args.opt_value_from_str(["-l", "--leaderboard"])?,
+        user: args.opt_value_from_str(["-u", "--user"])?,
+        dry_run: args.contains("--dry-run"),
+    })
+}
+
+/// Solved parts of the user in a saved leaderboard JSON.
+fn from_leaderboard(path: &str, user: Option<u64>) -> Result<BTreeMap<u8, (bool, bool)>, String> {
+    let json =
+        fs::read_to_string(path).map_err(|e| format!("could not read \"{}\": {}", path, e))?;
+    let board = leaderboard::parse(&json)?;
+
+    let member = match user {
+        Some(id) => board.members.iter().find(|m| m.id == id),
+        None if board.members.len() == 1 => board.members.first(),
+        None => return Err("the leaderboard has several members, pass --user <id>".into()),
+    };
+
+    member
+        .map(leaderboard::Member::solved)
+        .ok_or_else(|| format!("user #{} is not a member of the leaderboard", user.unwrap()))
+}
+
+/// Solved parts according to the recorded answers.
+/// An empty store is an error, it would replace the stars table with an empty one.
+fn from_answers(recorded: &answers::Answers) -> Result<BTreeMap<u8, (bool, bool)>, String> {
+    if recorded.is_empty() {
+        return Err(format!(
+            "\"{}\" contains no answers. Run `cargo all --release --record` first or pass --leaderboard.",
+            answers::ANSWERS_PATH
+        ));
+    }
+
+    let mut stars = BTreeMap::new();
+    for (day, part) in recorded.keys() {
+        let entry = stars.entry(*day).or_insert((false, false));
+        match part {
+            1 => entry.0 = true,
+            2 => entry.1 = true,
+            _ => {}
+        }
+    }
+    Ok(stars)
+}
+
+fn main() {
+    config::require();
+
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let stars = match &args.leaderboard {
+        Some(path) => from_leaderboard(path, args.user),
+        None => answers::read()
+            .map_err(|e| {
+                format!(
+                    "Failed to read \"{}\": {}. Run `cargo all --release --record` first or pass --leaderboard.",
+                    answers::ANSWERS_PATH,
+                    e
+                )
+            })
+            .and_then(|recorded| from_answers(&recorded)),
+    };
+    let stars = match stars {
+        Ok(stars) => stars,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let table = readme::render_stars(args.year, &stars);
+
+    if args.dry_run {
+        print!("{}", table);
+        return;
+    }
+
+    if let Err(e) = readme::write_block(readme::STARS_MARKER, &table) {
+        eprintln!("Failed to update \"{}\": {}", readme::README_PATH, e);
+        process::exit(1);
+    }
+
+    let count: usize = stars
+        .values()
+        .map(|(one, two)| usize::from(*one) + usize::from(*two))
+        .sum();
+    println!(
+        "🎄 Updated stars table in \"{}\" ({} stars).",
+        readme::README_PATH,
+        count
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_answers() {
+        assert!(from_answers(&answers::Answers::new()).is_err());
+
+        let recorded = answers::parse("01 1 24000\n01 2 45000\n03 1 157\n");
+        let stars = from_answers(&recorded).unwrap();
+        assert_eq!(stars[&1], (true, true));
+        assert_eq!(stars[&3], (true, false));
+        assert!(!stars.contains_key(&2));
+    }
+}
@@ -0,0 +1,202 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{answers, aoc_cli, config, readme, registry, ANSI_BOLD, ANSI_RESET};
+use std::{fs, process, process::Command};
+
+struct Args {
+    skip_tests: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        skip_tests: args.contains("--skip-tests"),
+    })
+}
+
+enum TestRun {
+    Passed(usize),
+    Failed,
+    Empty,
+    Skipped,
+}
+
+struct DayStatus {
+    day: u8,
+    module: bool,
+    registered: bool,
+    example: bool,
+    input: bool,
+    puzzle: bool,
+    tests: TestRun,
+    answers: usize,
+    stars: usize,
+}
+
+/// Whether the file exists and is not empty.
+fn has_contents(path: &str) -> bool {
+    fs::metadata(path).map(|m| m.len() > 0).unwrap_or(false)
+}
+
+fn run_tests(day: u8) -> TestRun {
+    let output = match Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &format!("{:02}", day)])
+        .output()
+    {
+        Ok(output) => output,
+        Err(_) => return TestRun::Skipped,
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let passed = stdout
+        .lines()
+        .find_map(|l| l.strip_prefix("test result: ok. "))
+        .and_then(|l| l.split(' ').next())
+        .and_then(|n| n.parse().ok());
+
+    match passed {
+        _ if !output.status.success() => TestRun::Failed,
+        Some(0) | None => TestRun::Empty,
+        Some(passed) => TestRun::Passed(passed),
+    }
+}
+
+fn mark(present: bool) -> &'static str {
+    if present {
+        "✔"
+    } else {
+        "·"
+    }
+}
+
+/// Human readable descriptions of artefacts that contradict each other.
+fn inconsistencies(status: &DayStatus) -> Vec<String> {
+    let mut problems = vec![];
+
+    if status.stars > 0 && !status.module {
+        problems.push(format!(
+            "{} star(s) in README but no module in src/bin",
+            status.stars
+        ));
+    }
+    if status.answers > 0 && !status.module {
+        problems.push("answers recorded but no module in src/bin".into());
+    }
+    if status.module && !status.registered {
+        problems.push(format!(
+            "module exists but is not registered in \"{}\"",
+            registry::REGISTRY_PATH
+        ));
+    }
+    if status.registered && !status.module {
+        problems.push(format!(
+            "registered in \"{}\" but no module in src/bin",
+            registry::REGISTRY_PATH
+        ));
+    }
+    if status.module && !status.example {
+        problems.push("module exists but the example file is missing or empty".into());
+    }
+    if matches!(status.tests, TestRun::Failed) {
+        problems.push("example tests fail".into());
+    }
+
+    problems
+}
+
+fn main() {
+    config::require();
+
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let registered = registry::load();
+    let recorded = answers::load();
+    let stars = fs::read_to_string(readme::README_PATH)
+        .map(|readme| readme::parse_stars(&readme))
+        .unwrap_or_default();
+
+    let statuses: Vec<DayStatus> = (1..=25)
+        .map(|day| {
+            let day_padded = format!("{:02}", day);
+            let module = fs::metadata(format!("src/bin/{}.rs", day_padded)).is_ok();
+            DayStatus {
+                day,
+                module,
+                registered: registered.contains(&day),
+                example: has_contents(&aoc_cli::get_example_path(day)),
+                input: has_contents(&aoc_cli::get_input_path(day)),
+                puzzle: has_contents(&aoc_cli::get_puzzle_path(day)),
+                tests: if module && !args.skip_tests {
+                    run_tests(day)
+                } else {
+                    TestRun::Skipped
+                },
+                answers: recorded.keys().filter(|(d, _)| *d == day).count(),
+                stars: stars
+                    .iter()
+                    .find(|(d, _)| *d == day)
+                    .map_or(0, |(_, count)| *count),
+            }
+        })
+        .filter(|s| {
+            s.module
+                || s.registered
+                || s.example
+                || s.input
+                || s.puzzle
+                || s.answers > 0
+                || s.stars > 0
+        })
+        .collect();
+
+    println!(
+        "{}Day | Module | Example | Input | Puzzle | Tests  | Answers | Stars{}",
+        ANSI_BOLD, ANSI_RESET
+    );
+
+    for status in &statuses {
+        let tests = match status.tests {
+            TestRun::Passed(n) => format!("✔ {}", n),
+            TestRun::Failed => "FAIL".into(),
+            TestRun::Empty => "none".into(),
+            TestRun::Skipped => "-".into(),
+        };
+        println!(
+            " {:02} |   {}    |    {}    |   {}   |   {}    | {:<6} |   {}/2   | {}",
+            status.day,
+            mark(status.module),
+            mark(status.example),
+            mark(status.input),
+            mark(status.puzzle),
+            tests,
+            status.answers,
+            "⭐".repeat(status.stars)
+        );
+    }
+
+    let problems: Vec<(u8, String)> = statuses
+        .iter()
+        .flat_map(|s| inconsistencies(s).into_iter().map(move |p| (s.day, p)))
+        .collect();
+
+    if problems.is_empty() {
+        println!("---");
+        println!("🎄 No inconsistencies found.");
+        return;
+    }
+
+    println!("---");
+    println!("{}Inconsistencies:{}", ANSI_BOLD, ANSI_RESET);
+    for (day, problem) in &problems {
+        println!("⚠️  Day {:02}: {}", day, problem);
+    }
+    process::exit(1);
+}
@@ -0,0 +1,213 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{aoc_cli, config, parse_part_outputs, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::{
+    fs,
+    process::{self, Command, Output},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn parse_args() -> Result<u8, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    args.free_from_str()
+}
+
+fn modified_times(paths: &[String]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+fn run_cargo(args: &[&str]) -> Option<Output> {
+    match Command::new("cargo").args(args).output() {
+        Ok(output) => Some(output),
+        Err(e) => {
+            eprintln!("Failed to call cargo: {}", e);
+            None
+        }
+    }
+}
+
+/// Names of the failed tests, taken from the `failures:` list that libtest prints before its summary.
+fn failed_tests(stdout: &str) -> Vec<&str> {
+    let lines: Vec<&str> = stdout.lines().collect();
+    // captured output is printed under an earlier `failures:` heading, the names under the last one.
+    let Some(start) = lines.iter().rposition(|l| *l == "failures:") else {
+        return Vec::new();
+    };
+    lines[start + 1..]
+        .iter()
+        .take_while(|l| !l.trim().is_empty())
+        .map(|l| l.trim())
+        .collect()
+}
+
+/// Prints the `test result:` line of a test run or the compiler errors if the build failed.
+/// Returns whether the day compiled.
+fn report_tests(output: &Output) -> bool {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let summary = stdout.lines().find(|l| l.starts_with("test result:"));
+
+    match summary {
+        Some(summary) => {
+            let icon = if output.status.success() {
+                "✅"
+            } else {
+                "❌"
+            };
+            println!(
+                "{} tests: {}",
+                icon,
+                summary.trim_start_matches("test result: ")
+            );
+            for failure in failed_tests(&stdout) {
+                println!("   {} FAILED", failure);
+            }
+            true
+        }
+        None => {
+            println!("❌ tests: build failed");
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            for line in stderr.lines().filter(|l| l.starts_with("error")) {
+                println!("   {}", line);
+            }
+            false
+        }
+    }
+}
+
+fn report_solution(output: &Output) {
+    if !output.status.success() {
+        println!("❌ solution exited with {}", output.status);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let panic = stderr.lines().skip_while(|l| !l.contains("panicked at"));
+        for line in panic.take(2) {
+            println!("   {}", line);
+        }
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for part in parse_part_outputs(&stdout) {
+        match part.answer {
+            Some(answer) => println!(
+                "🎄 Part {}: {} {}({:.2}ms){}",
+                part.part, answer, ANSI_ITALIC, part.elapsed_ms, ANSI_RESET
+            ),
+            None => println!("🎄 Part {}: not solved.", part.part),
+        }
+    }
+}
+
+fn run(day_padded: &str, iteration: u32) {
+    println!(
+        "{}---------- Day {} (run #{}) ----------{}",
+        ANSI_BOLD, day_padded, iteration, ANSI_RESET
+    );
+
+    let compiled = match run_cargo(&["test", "--quiet", "--bin", day_padded]) {
+        Some(output) => report_tests(&output),
+        None => return,
+    };
+
+    if !compiled {
+        return;
+    }
+
+    if let Some(output) = run_cargo(&["run", "--quiet", "--bin", day_padded]) {
+        report_solution(&output);
+    }
+}
+
+fn main() {
+    config::require();
+
+    let day = match parse_args() {
+        Ok(day) => day,
+        Err(_) => {
+            eprintln!("Need to specify a day (as integer). example: `cargo watch-day 7`");
+            process::exit(1);
+        }
+    };
+
+    let day_padded = format!("{:02}", day);
+    let paths = vec![
+        format!("src/bin/{}.rs", day_padded),
+        aoc_cli::get_example_path(day),
+        aoc_cli::get_input_path(day),
+        "src/helpers.rs".to_string(),
+    ];
+
+    if fs::metadata(&paths[0]).is_err() {
+        eprintln!(
+            "Module \"{}\" does not exist. Try `cargo scaffold {}` first.",
+            &paths[0], day
+        );
+        process::exit(1);
+    }
+
+    println!("👀 Watching {}", paths.join(", "));
+
+    let mut iteration = 1;
+    let mut last_modified = modified_times(&paths);
+    run(&day_padded, iteration);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let modified = modified_times(&paths);
+        if modified != last_modified {
+            last_modified = modified;
+            iteration += 1;
+            run(&day_padded, iteration);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `cargo test --quiet` output with two failing tests.
+    const FAILED_RUN: &str = "
+running 3 tests
+F.F
+failures:
+
+---- tests::test_part_one stdout ----
+
+thread 'tests::test_part_one' panicked at src/bin/01.rs:4:18:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::test_part_two stdout ----
+
+thread 'tests::test_part_two' panicked at src/bin/01.rs:10:9:
+boom
+
+
+failures:
+    tests::test_part_one
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+
+    #[test]
+    fn test_failed_tests() {
+        assert_eq!(
+            failed_tests(FAILED_RUN),
+            vec!["tests::test_part_one", "tests::test_part_two"]
+        );
+        assert!(failed_tests(
+            "\nrunning 2 tests\n..\ntest result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n"
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{env, fs, io, path::PathBuf, process, sync::OnceLock, time::Duration};
+
+/// Optional configuration file in the project root. Command line flags take precedence over it.
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// year of the event, `None` means the latest event.
+    pub year: Option<u16>,
+    pub inputs_dir: String,
+    pub examples_dir: String,
+    pub puzzles_dir: String,
+    /// session cookie passed to aoc-cli, `None` means its default `~/.adventofcode.session`.
+    pub session_file: Option<PathBuf>,
+    /// output format of `cargo all`, parsed where it is used.
+    pub format: String,
+    pub timeout: Duration,
+    pub jobs: usize,
+    /// whether `cargo all` builds and runs release binaries, `None` follows the profile of the runner (debug).
+    pub release: Option<bool>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            inputs_dir: "src/inputs".into(),
+            examples_dir: "src/examples".into(),
+            puzzles_dir: "src/puzzles".into(),
+            session_file: None,
+            format: "text".into(),
+            timeout: Duration::from_secs(60),
+            jobs: 1,
+            release: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn input_path(&self, day: u8) -> String {
+        format!("{}/{:02}.txt", self.inputs_dir, day)
+    }
+
+    pub fn example_path(&self, day: u8) -> String {
+        format!("{}/{:02}.txt", self.examples_dir, day)
+    }
+
+    pub fn puzzle_path(&self, day: u8) -> String {
+        format!("{}/{:02}.md", self.puzzles_dir, day)
+    }
+
+    pub fn release(&self) -> bool {
+        self.release.unwrap_or(cfg!(not(debug_assertions)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum TomlValue {
+    String(String),
+    Integer(i64),
+    Bool(bool),
+}
+
+/// Parses a value of the supported TOML subset: basic strings, integers and booleans.
+fn parse_value(raw: &str) -> Result<(TomlValue, &str), String> {
+    if let Some(rest) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Ok((TomlValue::String(value), &rest[i + 1..])),
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, c)) => value.push(c),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        return Err("unterminated string".into());
+    }
+
+    let end = raw
+        .find(|c: char| c.is_whitespace() || c == '#')
+        .unwrap_or(raw.len());
+    let (token, rest) = raw.split_at(end);
+    let value = match token {
+        "true" => TomlValue::Bool(true),
+        "false" => TomlValue::Bool(false),
+        _ => TomlValue::Integer(
+            token
+                .replace('_', "")
+                .parse()
+                .map_err(|_| format!("unsupported value \"{}\"", token))?,
+        ),
+    };
+    Ok((value, rest))
+}
+
+/// Key-value pairs of a TOML document, with keys prefixed by their table, e.g. `paths.inputs`.
+fn parse_toml(toml: &str) -> Result<Vec<(String, TomlValue)>, String> {
+    let mut table = String::new();
+    let mut entries = vec![];
+
+    for (i, line) in toml.lines().enumerate() {
+        let error = |e: String| format!("line {}: {}", i + 1, e);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let (name, rest) = name
+                .split_once(']')
+                .ok_or_else(|| error("unterminated table header".into()))?;
+            if !rest.trim().is_empty() && !rest.trim().starts_with('#') {
+                return Err(error(format!("unexpected \"{}\"", rest.trim())));
+            }
+            table = name.trim().to_string();
+            continue;
+        }
+
+        let (key, raw) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`".into()))?;
+        let (value, rest) = parse_value(raw.trim()).map_err(error)?;
+        if !rest.trim().is_empty() && !rest.trim().starts_with('#') {
+            return Err(error(format!("unexpected \"{}\"", rest.trim())));
+        }
+
+        let key = key.trim().trim_matches('"');
+        let key = if table.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", table, key)
+        };
+        entries.push((key, value));
+    }
+
+    Ok(entries)
+}
+
+/// Home directory of the current user.
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Expands a leading `~` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix('~') {
+        Some(rest) => home_dir()
+            .unwrap_or_default()
+            .join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(path),
+    }
+}
+
+const KEYS: [&str; 9] = [
+    "year",
+    "paths.inputs",
+    "paths.examples",
+    "paths.puzzles",
+    "paths.session_file",
+    "runner.format",
+    "runner.timeout",
+    "runner.jobs",
+    "runner.release",
+];
+
+pub fn parse(toml: &str) -> Result<Config, String> {
+    let mut config = Config::default();
+
+    for (key, value) in parse_toml(toml)? {
+        if !KEYS.contains(&key.as_str()) {
+            return Err(format!("unknown key \"{}\"", key));
+        }
+        let invalid = || format!("invalid value for \"{}\": {:?}", key, value);
+
+        match (key.as_str(), &value) {
+            ("year", TomlValue::Integer(year)) => {
+                config.year = Some(u16::try_from(*year).map_err(|_| invalid())?);
+            }
+            ("paths.inputs", TomlValue::String(dir)) => config.inputs_dir = dir.clone(),
+            ("paths.examples", TomlValue::String(dir)) => config.examples_dir = dir.clone(),
+            ("paths.puzzles", TomlValue::String(dir)) => config.puzzles_dir = dir.clone(),
+            ("paths.session_file", TomlValue::String(path)) => {
+                config.session_file = Some(expand_home(path));
+            }
+            ("runner.format", TomlValue::String(format)) => config.format = format.clone(),
+            ("runner.timeout", TomlValue::Integer(secs)) => {
+                let secs = u64::try_from(*secs).map_err(|_| invalid())?;
+                config.timeout = Duration::from_secs(secs);
+            }
+            ("runner.jobs", TomlValue::Integer(jobs)) => {
+                config.jobs = usize::try_from(*jobs).map_err(|_| invalid())?;
+            }
+            ("runner.release", TomlValue::Bool(release)) => config.release = Some(*release),
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(config)
+}
+
+/// Reads [CONFIG_PATH], falling back to the defaults if it does not exist.
+pub fn load() -> Result<Config, String> {
+    match fs::read_to_string(CONFIG_PATH) {
+        Ok(toml) => parse(&toml),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn loaded() -> &'static Result<Config, String> {
+    static CONFIG: OnceLock<Result<Config, String>> = OnceLock::new();
+    CONFIG.get_or_init(load)
+}
+
+/// The configuration of this project, loaded once.
+/// If the file is invalid, a warning is printed and the defaults are used, so solutions and tests keep working.
+pub fn get() -> &'static Config {
+    static FALLBACK: OnceLock<Config> = OnceLock::new();
+    match loaded() {
+        Ok(config) => config,
+        Err(e) => FALLBACK.get_or_init(|| {
+            eprintln!(
+                "Warning: failed to read \"{}\": {}. Using the default settings.",
+                CONFIG_PATH, e
+            );
+            Config::default()
+        }),
+    }
+}
+
+/// Like [get], but exits the process if the file is invalid. Used by the command line helpers.
+pub fn require() -> &'static Config {
+    match loaded() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {}", CONFIG_PATH, e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = parse(
+            "# comment\nyear = 2022\n\n[paths]\ninputs = \"data/in\" # trailing\nsession_file = \"/tmp/session\"\n\n[runner]\nformat = \"json\"\ntimeout = 1_0\nrelease = false\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.input_path(7), "data/in/07.txt");
+        assert_eq!(config.example_path(7), "src/examples/07.txt");
+        assert_eq!(config.session_file, Some(PathBuf::from("/tmp/session")));
+        assert_eq!(config.format, "json");
+        assert_eq!(config.timeout, Duration::from_secs(10));
+        assert_eq!(config.jobs, 1);
+        assert_eq!(config.release, Some(false));
+
+        assert_eq!(parse("").unwrap(), Config::default());
+        assert!(parse("yaer = 2022").unwrap_err().contains("unknown key"));
+        assert!(parse("year = \"2022\"").is_err());
+        assert!(parse("[runner]\ntimeout = -1").is_err());
+        assert!(parse("[paths]\ninputs = \"x").is_err());
+    }
+}
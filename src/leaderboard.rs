@@ -0,0 +1,291 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::json::{self, Value};
+use crate::{date, session, ANSI_BOLD, ANSI_RESET};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+const CACHE_DIR: &str = ".cache";
+
+/// The website asks to not request private leaderboards more often than every 15 minutes.
+pub const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    /// timestamps at which part one and two were solved, keyed by day.
+    pub completions: BTreeMap<u8, (Option<u64>, Option<u64>)>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Whether part one and two are solved, keyed by day.
+    pub fn solved(&self) -> BTreeMap<u8, (bool, bool)> {
+        self.completions
+            .iter()
+            .map(|(day, (one, two))| (*day, (one.is_some(), two.is_some())))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub members: Vec<Member>,
+}
+
+fn star_ts(level: Option<&Value>, part: &str) -> Option<u64> {
+    level?.get(part)?.get("get_star_ts")?.as_u64()
+}
+
+pub fn parse(input: &str) -> Result<Leaderboard, String> {
+    let value = json::parse(input)?;
+    let year = value
+        .get("event")
+        .and_then(Value::as_u64)
+        .ok_or("missing \"event\"")? as u16;
+
+    let mut members: Vec<Member> = value
+        .get("members")
+        .and_then(Value::as_object)
+        .ok_or("missing \"members\"")?
+        .values()
+        .map(|member| {
+            let completions = member
+                .get("completion_day_level")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .filter_map(|(day, level)| {
+                    Some((
+                        day.parse().ok()?,
+                        (star_ts(Some(level), "1"), star_ts(Some(level), "2")),
+                    ))
+                })
+                .collect();
+
+            Member {
+                id: member.get("id").and_then(Value::as_u64).unwrap_or_default(),
+                name: member.get("name").and_then(Value::as_str).map(String::from),
+                local_score: member
+                    .get("local_score")
+                    .and_then(Value::as_u64)
+                    .unwrap_or_default(),
+                stars: member
+                    .get("stars")
+                    .and_then(Value::as_u64)
+                    .unwrap_or_default(),
+                completions,
+            }
+        })
+        .collect();
+
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.id.cmp(&b.id))
+    });
+
+    Ok(Leaderboard { year, members })
+}
+
+/// Unix timestamp at which a puzzle unlocks: midnight EST (UTC-5) on the given day of december.
+pub fn unlock_ts(year: u16, day: u8) -> u64 {
+    let days = date::days_from_civil(i64::from(year), 12, u32::from(day));
+    (days * 86_400 + 5 * 3_600) as u64
+}
+
+/// Formats a duration in seconds as `HH:MM:SS`, prefixed with the number of days if longer than a day.
+pub fn format_duration(secs: u64) -> String {
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3_600,
+        rest % 3_600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{}d {}", days, time)
+    } else {
+        time
+    }
+}
+
+pub fn render_standings(leaderboard: &Leaderboard) -> String {
+    let mut out = format!("{} #  Score  Stars  Name{}\n", ANSI_BOLD, ANSI_RESET);
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        out.push_str(&format!(
+            "{:>2}  {:>5}  {:>5}  {}\n",
+            rank + 1,
+            member.local_score,
+            member.stars,
+            member.display_name()
+        ));
+    }
+    out
+}
+
+/// Per-day table of the time each member needed for both parts after the puzzle unlocked,
+/// and the time between solving part one and part two.
+pub fn render_days(leaderboard: &Leaderboard) -> String {
+    let days: Vec<u8> = leaderboard
+        .members
+        .iter()
+        .flat_map(|m| m.completions.keys().copied())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut out = String::new();
+    for day in days {
+        let unlock = unlock_ts(leaderboard.year, day);
+        let mut rows: Vec<(&Member, Option<u64>, Option<u64>)> = leaderboard
+            .members
+            .iter()
+            .filter_map(|m| {
+                let (one, two) = m.completions.get(&day)?;
+                // timestamps before the unlock (bad data or another year) are shown as `-`.
+                let since_unlock = |ts: &Option<u64>| ts.and_then(|ts| ts.checked_sub(unlock));
+                Some((m, since_unlock(one), since_unlock(two)))
+            })
+            .collect();
+        rows.sort_by_key(|(_, one, two)| (two.is_none(), *two, *one));
+
+        let name_width = rows
+            .iter()
+            .map(|(m, _, _)| m.display_name().chars().count())
+            .max()
+            .unwrap_or_default()
+            .max(4);
+
+        out.push_str(&format!(
+            "{}Day {:<w$}  {:>12}  {:>12}  {:>12}{}\n",
+            ANSI_BOLD,
+            day,
+            "Part 1",
+            "Part 2",
+            "Delta",
+            ANSI_RESET,
+            w = name_width.saturating_sub(3)
+        ));
+        let cell = |secs: Option<u64>| secs.map_or("-".into(), format_duration);
+        for (member, one, two) in rows {
+            let delta = one.zip(two).and_then(|(one, two)| two.checked_sub(one));
+            out.push_str(&format!(
+                "{:<w$}  {:>12}  {:>12}  {:>12}\n",
+                member.display_name(),
+                cell(one),
+                cell(two),
+                cell(delta),
+                w = name_width + 1
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub fn cache_path(year: u16, id: u64) -> PathBuf {
+    PathBuf::from(CACHE_DIR).join(format!("leaderboard-{}-{}.json", year, id))
+}
+
+/// Contents of the cached leaderboard if it is younger than [CACHE_TTL].
+pub fn read_cache(year: u16, id: u64) -> Option<String> {
+    let path = cache_path(year, id);
+    let modified = fs::metadata(&path).ok()?.modified().ok()?;
+    if SystemTime::now().duration_since(modified).ok()? > CACHE_TTL {
+        return None;
+    }
+    fs::read_to_string(path).ok()
+}
+
+pub fn write_cache(year: u16, id: u64, contents: &str) -> std::io::Result<()> {
+    fs::create_dir_all(CACHE_DIR)?;
+    fs::write(cache_path(year, id), contents)
+}
+
+/// Downloads the leaderboard JSON, authenticated by the stored session cookie.
+pub fn fetch(year: u16, id: u64) -> Result<String, String> {
+    let token = session::read().map_err(|e| e.to_string())?;
+    let path = format!("/{}/leaderboard/private/view/{}.json", year, id);
+
+    let (status, body) = session::get(&path, &token).map_err(|e| e.to_string())?;
+    match status {
+        Some(404) => return Err(format!("leaderboard {} does not exist for {}.", id, year)),
+        Some(status) if status >= 400 => {
+            return Err(session::SessionError::Server(status).to_string())
+        }
+        _ => {}
+    }
+
+    // the website redirects to the leaderboard overview if the session is invalid or has no access.
+    if !body.trim_start().starts_with('{') {
+        return Err("the response is not JSON. Is the session cookie valid (`cargo session check`) and do you have access to this leaderboard?".into());
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strip_ansi;
+
+    const FIXTURE: &str = include_str!("fixtures/leaderboard.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = parse(FIXTURE).unwrap();
+
+        assert_eq!(leaderboard.year, 2022);
+        let names: Vec<_> = leaderboard
+            .members
+            .iter()
+            .map(Member::display_name)
+            .collect();
+        assert_eq!(names, vec!["Alice", "Bob", "(anonymous user #1003)"]);
+        assert_eq!(
+            leaderboard.members[1].completions[&2],
+            (Some(1669958400), None)
+        );
+    }
+
+    #[test]
+    fn test_unlock_ts() {
+        assert_eq!(unlock_ts(2022, 1), 1669870800);
+        assert_eq!(unlock_ts(2022, 2), 1669957200);
+    }
+
+    #[test]
+    fn test_render_days() {
+        let rendered = strip_ansi(&render_days(&parse(FIXTURE).unwrap()));
+        let lines: Vec<_> = rendered.lines().collect();
+
+        assert_eq!(lines[1], "Alice       00:05:00      00:10:00      00:05:00");
+        assert_eq!(lines[2], "Bob         00:03:00      00:30:00      00:27:00");
+        assert_eq!(lines[5], "Alice       00:10:00   1d 00:20:00   1d 00:10:00");
+        assert_eq!(lines[6], "Bob         00:20:00             -             -");
+
+        // all completions are before the unlock of the same day in 2023.
+        let mut leaderboard = parse(FIXTURE).unwrap();
+        leaderboard.year = 2023;
+        let rendered = strip_ansi(&render_days(&leaderboard));
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(lines[1], "Alice              -             -             -");
+    }
+}
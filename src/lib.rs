@@ -6,12 +6,27 @@
 use std::env;
 use std::fs;
 
+pub mod answers;
+pub mod config;
+pub mod date;
 pub mod helpers;
+pub mod json;
+pub mod leaderboard;
+pub mod puzzle;
+pub mod readme;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod session;
+pub mod template;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// When set, `solve!` only runs the part with this number. Used by the runner to isolate parts.
+pub const PART_ENV: &str = "AOC_PART";
+
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
@@ -36,15 +51,32 @@ macro_rules! solve {
             }
         }
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        let selected = std::env::var(advent_of_code::PART_ENV).ok();
+        if selected.map_or(true, |part| part == $part.to_string()) {
+            println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
+            print_result($solver, $input);
+        }
     }};
 }
 
+/// Parses a day of the event, e.g. with `pico_args::Arguments::free_from_fn`.
+pub fn parse_day(value: &str) -> Result<u8, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|day| registry::DAYS.contains(day))
+        .ok_or_else(|| format!("expected a day between 1 and 25, got \"{}\"", value))
+}
+
 pub fn read_file(folder: &str, day: u8) -> String {
     let cwd = env::current_dir().unwrap();
 
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
+    let config = config::get();
+    let filepath = match folder {
+        "inputs" => cwd.join(config.input_path(day)),
+        "examples" => cwd.join(config.example_path(day)),
+        _ => cwd.join("src").join(folder).join(format!("{:02}.txt", day)),
+    };
 
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
@@ -54,29 +86,89 @@ fn parse_time(val: &str, postfix: &str) -> f64 {
     val.split(postfix).next().unwrap().parse().unwrap()
 }
 
+/// parses the `(elapsed: <duration>)` suffix printed by `solve!` into milliseconds.
+fn parse_elapsed(line: &str) -> f64 {
+    let timing = line.split("(elapsed: ").last().unwrap();
+    // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    if timing.contains("ns)") {
+        0_f64 // range below rounding precision.
+    } else if timing.contains("µs)") {
+        parse_time(timing, "µs") / 1000_f64
+    } else if timing.contains("ms)") {
+        parse_time(timing, "ms")
+    } else if timing.contains("s)") {
+        parse_time(timing, "s") * 1000_f64
+    } else {
+        0_f64
+    }
+}
+
 pub fn parse_exec_time(output: &str) -> f64 {
     output.lines().fold(0_f64, |acc, l| {
         if !l.contains("elapsed:") {
             acc
         } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
+            acc + parse_elapsed(l)
         }
     })
 }
 
+/// Result of a single part as printed by `solve!`.
+#[derive(Debug, PartialEq)]
+pub struct PartOutput {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed_ms: f64,
+}
+
+pub(crate) fn strip_ansi(line: &str) -> String {
+    [ANSI_BOLD, ANSI_ITALIC, ANSI_RESET]
+        .iter()
+        .fold(line.to_string(), |acc, code| acc.replace(code, ""))
+}
+
+/// Number of the part if `line` is a part header printed by `solve!`.
+fn parse_part_header(line: &str) -> Option<u8> {
+    line.trim_matches(|c: char| c == '🎄' || c.is_whitespace())
+        .strip_prefix("Part ")?
+        .parse()
+        .ok()
+}
+
+/// Extracts answers and timings from the output of a solution binary.
+/// Answers can span several lines, e.g. letters drawn on a screen, and end with the timing.
+pub fn parse_part_outputs(output: &str) -> Vec<PartOutput> {
+    let mut parts = vec![];
+    let mut lines = output.lines().map(strip_ansi).peekable();
+
+    while let Some(line) = lines.next() {
+        let Some(part) = parse_part_header(&line) else {
+            continue;
+        };
+
+        let mut result = vec![];
+        let mut elapsed_ms = None;
+        while let Some(line) = lines.next_if(|l| parse_part_header(l).is_none()) {
+            if let Some((answer, _)) = line.rsplit_once(" (elapsed: ") {
+                result.push(answer.to_string());
+                elapsed_ms = Some(parse_elapsed(&line));
+                break;
+            }
+            result.push(line);
+        }
+
+        parts.push(PartOutput {
+            part,
+            // a multi-line answer is usually printed on its own lines, after an empty one.
+            answer: elapsed_ms.map(|_| result.join("\n").trim_matches('\n').to_string()),
+            elapsed_ms: elapsed_ms.unwrap_or_default(),
+        });
+    }
+
+    parts
+}
+
 /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
 #[cfg(test)]
 macro_rules! assert_approx_eq {
@@ -122,35 +214,161 @@ mod tests {
             10400.50_f64
         );
     }
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(parse_day("7"), Ok(7));
+        assert_eq!(parse_day("25"), Ok(25));
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("99").is_err());
+        assert!(parse_day("seven").is_err());
+    }
+
+    #[test]
+    fn test_parse_part_outputs() {
+        let output = format!(
+            "🎄 {}Part 1{} 🎄\n95437 {}(elapsed: 755µs){}\n🎄 {}Part 2{} 🎄\nnot solved.",
+            ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, ANSI_RESET, ANSI_BOLD, ANSI_RESET
+        );
+        let parts = parse_part_outputs(&output);
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].part, 1);
+        assert_eq!(parts[0].answer, Some("95437".into()));
+        assert_approx_eq!(parts[0].elapsed_ms, 0.755_f64);
+        assert_eq!(parts[1].part, 2);
+        assert_eq!(parts[1].answer, None);
+    }
+
+    #[test]
+    fn test_parse_multi_line_part_outputs() {
+        let output = format!(
+            "🎄 {}Part 1{} 🎄\n13140 {}(elapsed: 1.00ms){}\n🎄 {}Part 2{} 🎄\n\n##..\n#..# {}(elapsed: 2.00ms){}\n",
+            ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, ANSI_RESET, ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, ANSI_RESET
+        );
+        let parts = parse_part_outputs(&output);
+
+        assert_eq!(parts[0].answer, Some("13140".into()));
+        assert_eq!(parts[1].answer, Some("##..\n#..#".into()));
+        assert_approx_eq!(parts[1].elapsed_ms, 2.0_f64);
+
+        // a part that printed debug output but no answer.
+        let parts =
+            parse_part_outputs("🎄 Part 1 🎄\ndebug\nnot solved.\n🎄 Part 2 🎄\n1 (elapsed: 1ms)");
+        assert_eq!(parts[0].answer, None);
+        assert_eq!(parts[1].answer, Some("1".into()));
+    }
 }
 
 pub mod aoc_cli {
+    use crate::config;
     use std::{
         fmt::Display,
         fs::create_dir_all,
+        io::{self, BufRead, BufReader},
+        path::PathBuf,
         process::{Command, Output, Stdio},
     };
 
+    /// A failed invocation of aoc-cli.
+    #[derive(Debug)]
+    pub struct Failure {
+        pub args: Vec<String>,
+        /// `None` if aoc-cli was terminated by a signal.
+        pub code: Option<i32>,
+        pub stderr: String,
+    }
+
+    impl Display for Failure {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let code = self
+                .code
+                .map_or_else(|| "signal".to_string(), |code| code.to_string());
+            write!(f, "`aoc {}` exited with {}", self.args.join(" "), code)?;
+            match self.stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+                Some(line) => write!(f, ": {}", line.trim()),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Debug)]
     pub enum AocCliError {
         CommandNotFound,
-        CommandNotCallable,
-        BadExitStatus(Output),
-        IoError,
+        CommandNotCallable(io::Error),
+        BadSession(Failure),
+        NotUnlocked(Failure),
+        RateLimited(Failure),
+        BadExitStatus(Failure),
+        IoError(io::Error),
     }
 
     impl Display for AocCliError {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
                 AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-                AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-                AocCliError::BadExitStatus(_) => {
-                    write!(f, "aoc-cli exited with a non-zero status.")
+                AocCliError::CommandNotCallable(e) => {
+                    write!(f, "aoc-cli could not be called: {}", e)
+                }
+                AocCliError::BadSession(failure) => write!(
+                    f,
+                    "the session cookie was rejected. Run `cargo session check` to verify it. ({})",
+                    failure
+                ),
+                AocCliError::NotUnlocked(failure) => {
+                    write!(f, "the puzzle is not unlocked yet. ({})", failure)
+                }
+                AocCliError::RateLimited(failure) => write!(
+                    f,
+                    "too many requests, wait a moment before trying again. ({})",
+                    failure
+                ),
+                AocCliError::BadExitStatus(failure) => write!(f, "{}", failure),
+                AocCliError::IoError(e) => {
+                    write!(f, "could not write output files to file system: {}", e)
                 }
-                AocCliError::IoError => write!(f, "could not write output files to file system."),
             }
         }
     }
 
+    impl std::error::Error for AocCliError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                AocCliError::CommandNotCallable(e) | AocCliError::IoError(e) => Some(e),
+                _ => None,
+            }
+        }
+    }
+
+    /// Sorts a failure into the common reasons based on the messages of aoc-cli and the website.
+    pub fn classify(failure: Failure) -> AocCliError {
+        let stderr = failure.stderr.to_lowercase();
+        let mentions = |patterns: &[&str]| patterns.iter().any(|p| stderr.contains(p));
+
+        if mentions(&[
+            "429",
+            "too many requests",
+            "answer too recently",
+            "rate limit",
+        ]) {
+            AocCliError::RateLimited(failure)
+        } else if mentions(&["still locked", "not available yet", "before it unlocks"]) {
+            AocCliError::NotUnlocked(failure)
+        } else if mentions(&[
+            "invalid session",
+            "session cookie file not found",
+            "failed to read session",
+            "unauthorized",
+            "400 bad request",
+            "please log in",
+            "puzzle inputs differ by user",
+        ]) {
+            AocCliError::BadSession(failure)
+        } else {
+            AocCliError::BadExitStatus(failure)
+        }
+    }
+
     pub fn check() -> Result<(), AocCliError> {
         Command::new("aoc")
             .arg("-V")
@@ -160,16 +378,16 @@ pub mod aoc_cli {
     }
 
     pub fn read(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        // TODO: output local puzzle if present.
         let args = build_args("read", &[], day, year);
-        call_aoc_cli(&args)
+        let output = call_aoc_cli(&args)?;
+        check_status(&args, output)
     }
 
     pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
         let input_path = get_input_path(day);
 
         let puzzle_path = get_puzzle_path(day);
-        create_dir_all("src/puzzles").map_err(|_| AocCliError::IoError)?;
+        create_dir_all(&config::get().puzzles_dir).map_err(AocCliError::IoError)?;
 
         let args = build_args(
             "download",
@@ -184,32 +402,43 @@ pub mod aoc_cli {
             year,
         );
 
-        let output = call_aoc_cli(&args)?;
+        let output = check_status(&args, call_aoc_cli(&args)?)?;
 
-        if output.status.success() {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-            Ok(output)
-        } else {
-            Err(AocCliError::BadExitStatus(output))
-        }
+        println!("---");
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(output)
     }
 
-    fn get_input_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("src/inputs/{}.txt", day_padded)
+    pub fn get_input_path(day: u8) -> String {
+        config::get().input_path(day)
     }
 
-    fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("src/puzzles/{}.md", day_padded)
+    pub fn get_example_path(day: u8) -> String {
+        config::get().example_path(day)
+    }
+
+    pub fn get_puzzle_path(day: u8) -> String {
+        config::get().puzzle_path(day)
+    }
+
+    /// Location of the session cookie used by aoc-cli.
+    pub fn session_path() -> Option<PathBuf> {
+        if let Some(path) = &config::get().session_file {
+            return Some(path.clone());
+        }
+        Some(config::home_dir()?.join(".adventofcode.session"))
     }
 
     fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
         let mut cmd_args = args.to_vec();
 
-        if let Some(year) = year {
+        if let Some(session_file) = &config::get().session_file {
+            cmd_args.push("--session-file".into());
+            cmd_args.push(session_file.display().to_string());
+        }
+
+        if let Some(year) = year.or(config::get().year) {
             cmd_args.push("--year".into());
             cmd_args.push(year.to_string());
         }
@@ -219,16 +448,85 @@ pub mod aoc_cli {
         cmd_args
     }
 
+    fn check_status(args: &[String], output: Output) -> Result<Output, AocCliError> {
+        if output.status.success() {
+            return Ok(output);
+        }
+        Err(classify(Failure {
+            args: args.to_vec(),
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }))
+    }
+
+    /// Runs aoc-cli with stdout passed through. stderr is shown as well, but also captured for error reporting.
     fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
         if cfg!(debug_assertions) {
             println!("Calling >aoc with: {}", args.join(" "));
         }
 
-        Command::new("aoc")
+        let mut child = Command::new("aoc")
             .args(args)
             .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map_err(|_| AocCliError::CommandNotCallable)
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(AocCliError::CommandNotCallable)?;
+
+        let mut stderr = String::new();
+        if let Some(pipe) = child.stderr.take() {
+            for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                eprintln!("{}", line);
+                stderr.push_str(&line);
+                stderr.push('\n');
+            }
+        }
+
+        let status = child.wait().map_err(AocCliError::CommandNotCallable)?;
+        Ok(Output {
+            status,
+            stdout: vec![],
+            stderr: stderr.into_bytes(),
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn failure(stderr: &str) -> Failure {
+            Failure {
+                args: vec!["--day".into(), "1".into(), "download".into()],
+                code: Some(1),
+                stderr: stderr.into(),
+            }
+        }
+
+        #[test]
+        fn test_classify() {
+            assert!(matches!(
+                classify(failure("Loaded session cookie from \"~/.adventofcode.session\".\nError: Invalid session cookie")),
+                AocCliError::BadSession(_)
+            ));
+            assert!(matches!(
+                classify(failure("Error: Puzzle 25 of 2022 is still locked")),
+                AocCliError::NotUnlocked(_)
+            ));
+            assert!(matches!(
+                classify(failure("HTTP status client error (429 Too Many Requests)")),
+                AocCliError::RateLimited(_)
+            ));
+            assert!(matches!(
+                classify(failure("Loaded session cookie from \"~/.adventofcode.session\".\nError: something else")),
+                AocCliError::BadExitStatus(_)
+            ));
+        }
+
+        #[test]
+        fn test_display_failure() {
+            assert_eq!(
+                failure("Loaded session cookie\nError: something else\n\n").to_string(),
+                "`aoc --day 1 download` exited with 1: Error: something else"
+            );
+        }
     }
 }
@@ -2,42 +2,214 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::answers;
+use advent_of_code::config;
+use advent_of_code::readme;
+use advent_of_code::registry;
+use advent_of_code::report::{self, Format};
+use advent_of_code::runner::{self, DayReport, PartReport, Status};
 use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use std::{path::Path, process, time::Duration};
+
+const BENCHMARK_MARKER: &str = "<!--- benchmarking table --->";
+
+struct Args {
+    timeout: Duration,
+    jobs: usize,
+    serial_timing: bool,
+    format: Format,
+    update_readme: bool,
+    record: bool,
+    release: bool,
+}
+
+fn parse_args(mut args: pico_args::Arguments) -> Result<Args, pico_args::Error> {
+    let config = config::require();
+    let parsed = Args {
+        timeout: args
+            .opt_value_from_str(["-t", "--timeout"])?
+            .map(Duration::from_secs)
+            .unwrap_or(config.timeout),
+        jobs: args
+            .opt_value_from_str(["-j", "--jobs"])?
+            .unwrap_or(config.jobs),
+        serial_timing: args.contains("--serial-timing"),
+        update_readme: args.contains("--update-readme"),
+        record: args.contains("--record"),
+        format: match args.opt_value_from_str(["-f", "--format"])? {
+            Some(format) => format,
+            None => config
+                .format
+                .parse()
+                .map_err(|e| pico_args::Error::ArgumentParsingFailed {
+                    cause: format!("{} in \"{}\"", e, config::CONFIG_PATH),
+                })?,
+        },
+        release: match (
+            args.contains("--release") | args.contains("--release-bins"),
+            args.contains("--debug-bins"),
+        ) {
+            (true, _) => true,
+            (_, true) => false,
+            _ => config.release(),
+        },
+    };
+
+    // a typo would otherwise silently fall back to the defaults.
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        let remaining: Vec<_> = remaining.iter().map(|arg| arg.to_string_lossy()).collect();
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected arguments: {}", remaining.join(" ")),
+        });
+    }
+    Ok(parsed)
+}
+
+fn print_part(report: &PartReport) {
+    match &report.status {
+        // the solution is not started without an input.
+        Status::Unsolved if report.output.is_empty() => {
+            println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, report.part, ANSI_RESET);
+            println!("not solved, no input.");
+        }
+        Status::Solved { .. } | Status::Unsolved => println!("{}", report.output.trim()),
+        Status::Panicked(message) => {
+            println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, report.part, ANSI_RESET);
+            println!("💥 panicked: {}", message);
+        }
+        Status::TimedOut(timeout) => {
+            println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, report.part, ANSI_RESET);
+            println!("⏱️ timed out after {:.2?}", timeout);
+        }
+    }
+}
+
+fn print_day(report: &DayReport) {
+    println!("----------");
+    println!("{}| Day {:02} |{}", ANSI_BOLD, report.day, ANSI_RESET);
+    println!("----------");
+
+    match &report.parts {
+        Some(parts) => parts.iter().for_each(print_part),
+        None => println!("Not solved."),
+    }
+}
+
+/// Rewrites the benchmark table between the markers of the readme at `path`.
+fn write_benchmarks(path: &Path, reports: &[DayReport]) -> std::io::Result<()> {
+    readme::write_block_at(path, BENCHMARK_MARKER, &report::to_benchmark_table(reports))
+}
 
 fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
+    let args = match parse_args(pico_args::Arguments::from_env()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let days = registry::load();
+    if !runner::build_all(&days, args.release) {
+        eprintln!("Failed to build solutions.");
+        process::exit(1);
+    }
+
+    let retime = args.serial_timing && args.jobs > 1;
+
+    let is_text = args.format == Format::Text;
 
-            let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
-                args.push("--release");
+    let bin_dir = runner::bin_dir(args.release);
+    let inputs_dir = Path::new(&config::get().inputs_dir);
+    let mut day_reports = runner::run_days(
+        &days,
+        &bin_dir,
+        inputs_dir,
+        args.jobs,
+        args.timeout,
+        |report| {
+            if is_text && !retime {
+                print_day(report);
             }
+        },
+    );
 
-            let cmd = Command::new("cargo").args(&args).output().unwrap();
+    if retime {
+        runner::retime(&mut day_reports, &bin_dir, args.timeout);
+        if is_text {
+            day_reports.iter().for_each(print_day);
+        }
+    }
 
-            println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
-            println!("----------");
+    let missing = day_reports.iter().filter(|r| r.parts.is_none()).count();
+    let reports: Vec<_> = day_reports
+        .iter()
+        .filter_map(|r| r.parts.as_ref())
+        .flatten()
+        .collect();
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
+    let failed = reports
+        .iter()
+        .any(|r| matches!(r.status, Status::Panicked(_) | Status::TimedOut(_)));
 
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
-                }
+    if args.record {
+        let solved: answers::Answers = reports
+            .iter()
+            .filter_map(|r| Some(((r.day, r.part), r.status.answer()?.to_string())))
+            .collect();
+        // an empty store would make `cargo stars` report no progress at all.
+        if solved.is_empty() {
+            eprintln!(
+                "No solved parts, \"{}\" was not changed.",
+                answers::ANSWERS_PATH
             );
+        } else {
+            match answers::record(solved) {
+                Ok(_) => eprintln!("🎄 Recorded answers in \"{}\".", answers::ANSWERS_PATH),
+                Err(e) => {
+                    eprintln!("Failed to record answers: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+    }
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+    if args.update_readme {
+        if !args.release {
+            eprintln!(
+                "Note: timings were measured with a debug build. Use `cargo all --release` or set `release = true` in \"{}\" for benchmarks.",
+                config::CONFIG_PATH
+            );
+        }
+        match write_benchmarks(Path::new(readme::README_PATH), &day_reports) {
+            Ok(_) => eprintln!("🎄 Updated benchmark table in \"{}\".", readme::README_PATH),
+            Err(e) => {
+                eprintln!("Failed to update \"{}\": {}", readme::README_PATH, e);
+                process::exit(1);
             }
+        }
+    }
+
+    match args.format {
+        Format::Text => {}
+        Format::Json => println!("{}", report::to_json(&day_reports)),
+        Format::Csv => print!("{}", report::to_csv(&day_reports)),
+        Format::Markdown => print!("{}", report::to_markdown(&day_reports)),
+    }
+
+    if !is_text {
+        if failed {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let total: f64 = reports
+        .iter()
+        .map(|report| match report.status {
+            Status::Solved { elapsed_ms, .. } => elapsed_ms,
+            _ => 0_f64,
         })
         .sum();
 
@@ -45,4 +217,146 @@ fn main() {
         "{}Total:{} {}{:.2}ms{}",
         ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
     );
+
+    let count = |f: fn(&Status) -> bool| reports.iter().filter(|r| f(&r.status)).count();
+    let panicked = count(|s| matches!(s, Status::Panicked(_)));
+    let timed_out = count(|s| matches!(s, Status::TimedOut(_)));
+
+    println!(
+        "Solved: {} | Unsolved: {} | Panicked: {} | Timed out: {}",
+        count(|s| matches!(s, Status::Solved { .. })),
+        count(|s| matches!(s, Status::Unsolved)) + missing * 2,
+        panicked,
+        timed_out
+    );
+
+    for report in reports {
+        match &report.status {
+            Status::Panicked(message) => println!(
+                "💥 Day {:02} Part {} panicked: {}",
+                report.day, report.part, message
+            ),
+            Status::TimedOut(timeout) => println!(
+                "⏱️ Day {:02} Part {} timed out after {:.2?}",
+                report.day, report.part, timeout
+            ),
+            _ => {}
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{ffi::OsString, fs};
+
+    /// Arguments that `cargo <alias> <flags>` passes to the binary, as cargo expands the alias.
+    fn through_alias(alias: &str, flags: &[&str]) -> Vec<OsString> {
+        let config = fs::read_to_string(".cargo/config").unwrap();
+        let expansion = config
+            .lines()
+            .find_map(|l| l.strip_prefix(&format!("{} = ", alias)))
+            .unwrap()
+            .trim_matches('"');
+        let mut args: Vec<&str> = expansion.split_whitespace().collect();
+        args.extend(flags);
+        args.iter()
+            .skip_while(|arg| **arg != "--")
+            .skip(1)
+            .map(OsString::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_all_alias_forwards_flags() {
+        let args = through_alias(
+            "all",
+            &[
+                "--timeout",
+                "5",
+                "--format",
+                "csv",
+                "--jobs",
+                "8",
+                "--release",
+            ],
+        );
+        let args = parse_args(pico_args::Arguments::from_vec(args)).unwrap();
+        assert_eq!(args.timeout, Duration::from_secs(5));
+        assert_eq!(args.format, Format::Csv);
+        assert_eq!(args.jobs, 8);
+        assert!(args.release);
+
+        let args = through_alias("all", &["--serial-timing", "--update-readme", "--record"]);
+        let args = parse_args(pico_args::Arguments::from_vec(args)).unwrap();
+        assert!(args.serial_timing && args.update_readme && args.record);
+
+        // the command that regenerates the benchmark table in the readme.
+        let args = through_alias(
+            "all",
+            &[
+                "--release",
+                "--update-readme",
+                "--jobs",
+                "4",
+                "--serial-timing",
+            ],
+        );
+        let args = parse_args(pico_args::Arguments::from_vec(args)).unwrap();
+        assert!(args.release && args.update_readme && args.serial_timing);
+        assert!(!args.record);
+
+        let args = through_alias("all", &["--timout", "5"]);
+        assert!(parse_args(pico_args::Arguments::from_vec(args)).is_err());
+    }
+
+    #[test]
+    fn test_write_benchmarks() {
+        let solved = |part, elapsed_ms| PartReport {
+            day: 1,
+            part,
+            output: String::new(),
+            status: Status::Solved {
+                answer: "42".into(),
+                elapsed_ms,
+            },
+        };
+        let reports = [
+            DayReport {
+                day: 1,
+                parts: Some(vec![solved(1, 1.5), solved(2, 0.25)]),
+            },
+            DayReport {
+                day: 2,
+                parts: None,
+            },
+        ];
+
+        let path = std::env::temp_dir().join(format!("aoc-readme-{}.md", std::process::id()));
+        let fixture = format!(
+            "# Title\n{0}\n## Benchmarks\nold table\n{0}\n\nThe table between the `{0}` markers is generated.\n",
+            BENCHMARK_MARKER
+        );
+        fs::write(&path, &fixture).unwrap();
+        write_benchmarks(&path, &reports).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+
+        assert_eq!(
+            written,
+            format!(
+                "# Title\n{0}\n## Benchmarks\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n| [Day 1](./src/bin/01.rs) | `1.50ms` | `250.00µs` |\n| Day 2 | missing | missing |\n\n**Total: 1.75ms**\n{0}\n\nThe table between the `{0}` markers is generated.\n",
+                BENCHMARK_MARKER
+            )
+        );
+
+        // a readme without the markers is left alone.
+        fs::write(&path, "# Title\n").unwrap();
+        assert!(write_benchmarks(&path, &reports).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Title\n");
+        fs::remove_file(&path).ok();
+    }
 }
@@ -0,0 +1,213 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{strip_ansi, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Extracts the title from the heading of a puzzle description, e.g. `Day 16: Proboscidea Volcanium`.
+pub fn parse_title(puzzle: &str) -> Option<String> {
+    let heading = puzzle.lines().find(|l| !l.trim().is_empty())?;
+    let title = heading
+        .trim_start_matches('\\')
+        .trim_matches(|c: char| c == '-' || c.is_whitespace());
+
+    if title.starts_with("Day ") {
+        Some(title.to_string())
+    } else {
+        None
+    }
+}
+
+/// Contents of all fenced code blocks, each paired with the paragraph preceding it.
+fn code_blocks(puzzle: &str) -> Vec<(String, String)> {
+    let mut blocks = vec![];
+    let mut paragraph = String::new();
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in puzzle.lines() {
+        match (&mut block, line.trim_end() == "```") {
+            (None, true) => block = Some(vec![]),
+            (Some(lines), true) => {
+                let mut content = lines.join("\n").trim_end().to_string();
+                content.push('\n');
+                blocks.push((paragraph.clone(), content));
+                block = None;
+            }
+            (Some(lines), false) => lines.push(line),
+            (None, false) if !line.trim().is_empty() => paragraph = line.to_string(),
+            (None, false) => {}
+        }
+    }
+
+    blocks
+}
+
+/// Extracts the example input from a puzzle description.
+/// This is the first code block introduced by a paragraph mentioning an example, or the first code block.
+pub fn parse_example(puzzle: &str) -> Option<String> {
+    let blocks = code_blocks(puzzle);
+
+    blocks
+        .iter()
+        .find(|(paragraph, _)| paragraph.to_lowercase().contains("example"))
+        .or_else(|| blocks.first())
+        .map(|(_, content)| content.clone())
+}
+
+/// Replaces the inline markdown of a line with ANSI styles: `*emphasis*` is bold, `` `code` `` is italic.
+/// Links are reduced to their text and backslash escapes are resolved.
+fn style_inline(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::new();
+    let (mut in_code, mut in_emphasis) = (false, false);
+
+    let restyle = |out: &mut String, in_code: bool, in_emphasis: bool| {
+        out.push_str(ANSI_RESET);
+        if in_code {
+            out.push_str(ANSI_ITALIC);
+        }
+        if in_emphasis {
+            out.push_str(ANSI_BOLD);
+        }
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let prev = if i > 0 { chars[i - 1] } else { ' ' };
+        let next = chars.get(i + 1).copied().unwrap_or(' ');
+
+        match c {
+            '\\' if i + 1 < chars.len() => {
+                out.push(next);
+                i += 1;
+            }
+            '`' => {
+                in_code = !in_code;
+                restyle(&mut out, in_code, in_emphasis);
+            }
+            '*' if !in_emphasis && !next.is_whitespace() => {
+                in_emphasis = true;
+                restyle(&mut out, in_code, in_emphasis);
+            }
+            '*' if in_emphasis && !prev.is_whitespace() => {
+                in_emphasis = false;
+                restyle(&mut out, in_code, in_emphasis);
+            }
+            '[' if !in_code => {
+                let rest: String = chars[i + 1..].iter().collect();
+                match rest.split_once("](").and_then(|(text, url)| {
+                    let (_, after) = url.split_once(')')?;
+                    Some((
+                        text.to_string(),
+                        rest.chars().count() - after.chars().count(),
+                    ))
+                }) {
+                    Some((text, consumed)) => {
+                        out.push_str(&style_inline(&text));
+                        if in_emphasis {
+                            restyle(&mut out, in_code, in_emphasis);
+                        }
+                        i += consumed;
+                    }
+                    None => out.push(c),
+                }
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+
+    if in_code || in_emphasis {
+        out.push_str(ANSI_RESET);
+    }
+    out
+}
+
+/// Wraps `text` at `width` visible characters, prefixing continuation lines with `indent`.
+fn wrap(text: &str, width: usize, indent: &str) -> String {
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut line_width = 0;
+
+    for word in text.split(' ') {
+        let word_width = strip_ansi(word).chars().count();
+        if line_width > 0 && line_width + 1 + word_width > width {
+            lines.push(line);
+            line = indent.to_string();
+            line_width = indent.len();
+        } else if line_width > 0 {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(word);
+        line_width += word_width;
+    }
+    lines.push(line);
+
+    lines.join("\n")
+}
+
+/// Renders a puzzle description for the terminal.
+pub fn render(puzzle: &str, width: usize) -> String {
+    let mut out = vec![];
+    let mut in_block = false;
+
+    for line in puzzle.lines() {
+        if line.trim_end() == "```" {
+            in_block = !in_block;
+            continue;
+        }
+
+        if in_block {
+            out.push(format!("    {}{}{}", ANSI_ITALIC, line, ANSI_RESET));
+        } else if line.starts_with("\\---") {
+            out.push(format!("{}{}{}", ANSI_BOLD, style_inline(line), ANSI_RESET));
+        } else if !line.is_empty() && line.chars().all(|c| c == '-') {
+            // setext underline of the heading.
+            continue;
+        } else if let Some(item) = line.strip_prefix("* ") {
+            out.push(wrap(&format!("• {}", style_inline(item)), width, "  "));
+        } else {
+            out.push(wrap(&style_inline(line), width, ""));
+        }
+    }
+
+    let mut rendered = out.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "\\--- Day 1: Test Puzzle ---\n----------\n\nThe shapes look like this:\n\n```\n####\n```\n\nFor example, suppose you had:\n\n```\n1\n2\n\n```\n\nDone.";
+
+    #[test]
+    fn test_parse_title() {
+        assert_eq!(parse_title(PUZZLE), Some("Day 1: Test Puzzle".into()));
+        assert_eq!(parse_title("no heading"), None);
+    }
+
+    #[test]
+    fn test_parse_example() {
+        assert_eq!(parse_example(PUZZLE), Some("1\n2\n".into()));
+        assert_eq!(parse_example("```\na\n```"), Some("a\n".into()));
+        assert_eq!(parse_example("no code"), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(
+            "\\--- Day 1: Test ---\n----------\n\nA *long* paragraph with `code`.\n\n```\n1 2\n```",
+            15,
+        );
+
+        assert_eq!(
+            strip_ansi(&rendered),
+            "--- Day 1: Test ---\n\nA long\nparagraph with\ncode.\n\n    1 2\n"
+        );
+        assert!(rendered.contains(&format!("{}{}long{}", ANSI_RESET, ANSI_BOLD, ANSI_RESET)));
+    }
+}
@@ -0,0 +1,115 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+pub const README_PATH: &str = "README.md";
+pub const STARS_MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Replaces everything between the two occurrences of `marker` with `content`.
+/// Returns `None` if `readme` does not contain the marker twice.
+pub fn update_block(readme: &str, marker: &str, content: &str) -> Option<String> {
+    let start = readme.find(marker)? + marker.len();
+    let end = start + readme[start..].find(marker)?;
+
+    Some(format!(
+        "{}\n{}\n{}",
+        &readme[..start],
+        content.trim(),
+        &readme[end..]
+    ))
+}
+
+/// Rewrites the block between the `marker` comments in `README.md`.
+pub fn write_block(marker: &str, content: &str) -> io::Result<()> {
+    write_block_at(Path::new(README_PATH), marker, content)
+}
+
+/// Like [write_block], for the readme at `path`.
+pub fn write_block_at(path: &Path, marker: &str, content: &str) -> io::Result<()> {
+    let readme = fs::read_to_string(path)?;
+    let updated = update_block(&readme, marker, content).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("could not find two \"{}\" markers", marker),
+        )
+    })?;
+    fs::write(path, updated)
+}
+
+/// Number of stars per day listed in the stars table, e.g. `| [Day 1](...) | ⭐ | ⭐ |`.
+pub fn parse_stars(readme: &str) -> Vec<(u8, usize)> {
+    let start = match readme.find(STARS_MARKER) {
+        Some(start) => start + STARS_MARKER.len(),
+        None => return vec![],
+    };
+    let block = &readme[start..];
+    let block = &block[..block.find(STARS_MARKER).unwrap_or(block.len())];
+
+    block
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once("[Day ")?;
+            let (day, _) = rest.split_once(']')?;
+            Some((day.parse().ok()?, line.matches('⭐').count()))
+        })
+        .collect()
+}
+
+/// Renders the stars table for the days in `stars`, which maps a day to whether part one and two are solved.
+pub fn render_stars(year: u16, stars: &BTreeMap<u8, (bool, bool)>) -> String {
+    let star = |solved: bool| if solved { "⭐" } else { " " };
+    let mut out = format!(
+        "## {} Results\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n",
+        year
+    );
+    for (day, (one, two)) in stars {
+        out.push_str(&format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} |\n",
+            star(*one),
+            star(*two),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_block() {
+        let marker = "<!--- table --->";
+        let readme = "# Title\n<!--- table --->\nold\n<!--- table --->\nfooter\n";
+
+        assert_eq!(
+            update_block(readme, marker, "new\n"),
+            Some("# Title\n<!--- table --->\nnew\n<!--- table --->\nfooter\n".into())
+        );
+        assert_eq!(
+            update_block("# Title\n<!--- table --->\n", marker, "new"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_stars() {
+        let readme = "<!--- advent_readme_stars table --->\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n| [Day 1](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ |\n| [Day 2](https://adventofcode.com/2022/day/2) | ⭐ |   |\n<!--- advent_readme_stars table --->\n| [Day 3](x) | ⭐ | ⭐ |";
+
+        assert_eq!(parse_stars(readme), vec![(1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn test_render_stars() {
+        let stars = BTreeMap::from([(1, (true, true)), (2, (true, false))]);
+        let rendered = render_stars(2022, &stars);
+
+        assert!(rendered.starts_with("## 2022 Results\n"));
+        assert!(rendered.ends_with("| [Day 2](https://adventofcode.com/2022/day/2) | ⭐ |   |\n"));
+        assert_eq!(
+            parse_stars(&format!("{0}\n{1}{0}", STARS_MARKER, rendered)),
+            vec![(1, 2), (2, 1)]
+        );
+    }
+}
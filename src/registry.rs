@@ -0,0 +1,137 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{collections::BTreeMap, fs, io};
+
+/// List of scaffolded days, maintained by `cargo scaffold` and read by `cargo all`.
+pub const REGISTRY_PATH: &str = "src/days.txt";
+
+const HEADER: &str =
+    "# Days scaffolded with `cargo scaffold`, one per line. Used by `cargo all` to find solutions.";
+
+/// Days of an event.
+pub const DAYS: std::ops::RangeInclusive<u8> = 1..=25;
+
+/// Registered days with the fingerprint of the module as scaffolded, if it was recorded.
+type Entries = BTreeMap<u8, Option<u64>>;
+
+fn parse_entries(registry: &str) -> Entries {
+    registry
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| {
+            let mut tokens = l.split_whitespace();
+            let day = tokens
+                .next()?
+                .parse()
+                .ok()
+                .filter(|day| DAYS.contains(day))?;
+            let fingerprint = tokens.next().and_then(|f| u64::from_str_radix(f, 16).ok());
+            Some((day, fingerprint))
+        })
+        .collect()
+}
+
+pub fn parse(registry: &str) -> Vec<u8> {
+    parse_entries(registry).into_keys().collect()
+}
+
+fn render(entries: &Entries) -> String {
+    let mut out = format!("{}\n", HEADER);
+    for (day, fingerprint) in entries {
+        match fingerprint {
+            Some(fingerprint) => out.push_str(&format!("{:02} {:016x}\n", day, fingerprint)),
+            None => out.push_str(&format!("{:02}\n", day)),
+        }
+    }
+    out
+}
+
+/// Stable hash of a module, used to tell whether it was changed since it was scaffolded.
+pub fn fingerprint(contents: &str) -> u64 {
+    // FNV-1a, unlike `DefaultHasher` it does not change between Rust versions.
+    contents.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Days with a module in `src/bin`, used if the registry does not exist yet.
+fn discover() -> Entries {
+    fs::read_dir("src/bin")
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let stem = name.strip_suffix(".rs")?;
+            if stem.len() == 2 {
+                stem.parse().ok().filter(|day| DAYS.contains(day))
+            } else {
+                None
+            }
+        })
+        .map(|day| (day, None))
+        .collect()
+}
+
+fn load_entries() -> Entries {
+    match fs::read_to_string(REGISTRY_PATH) {
+        Ok(registry) => parse_entries(&registry),
+        Err(_) => discover(),
+    }
+}
+
+pub fn load() -> Vec<u8> {
+    load_entries().into_keys().collect()
+}
+
+fn save(entries: &Entries) -> io::Result<()> {
+    fs::write(REGISTRY_PATH, render(entries))
+}
+
+/// Fingerprint of the module of `day` as it was scaffolded, `None` for days registered without one.
+pub fn scaffolded(day: u8) -> Option<u64> {
+    load_entries().get(&day).copied().flatten()
+}
+
+/// Adds `day` with the contents of its scaffolded module to the registry.
+/// Returns `false` if it was registered already.
+pub fn add(day: u8, module: &str) -> io::Result<bool> {
+    let mut entries = load_entries();
+    let added = !entries.contains_key(&day);
+    entries.insert(day, Some(fingerprint(module)));
+    save(&entries).map(|_| added)
+}
+
+/// Removes `day` from the registry. Returns `false` if it was not registered.
+pub fn remove(day: u8) -> io::Result<bool> {
+    let mut entries = load_entries();
+    if entries.remove(&day).is_none() {
+        return Ok(false);
+    }
+    save(&entries).map(|_| true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_render() {
+        let registry = "# comment\n07\n1 00000000000000ff\n\n15\n07\nnot a day\n0\n99\n";
+        assert_eq!(parse(registry), vec![1, 7, 15]);
+
+        let entries = parse_entries(registry);
+        assert_eq!(entries[&1], Some(255));
+        assert_eq!(entries[&7], None);
+        assert_eq!(parse_entries(&render(&entries)), entries);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(fingerprint("fn main() {}\n"), fingerprint("fn main() {}"));
+    }
+}
@@ -0,0 +1,272 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::runner::{DayReport, PartReport};
+use std::{str::FromStr, time::Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "unknown format \"{}\", expected one of: text, json, csv, markdown",
+                s
+            )),
+        }
+    }
+}
+
+const MISSING: &str = "missing";
+const MISSING_MESSAGE: &str = "no solution binary";
+
+/// One row per part, and a single row without a part for each day that has no solution binary.
+fn rows(reports: &[DayReport]) -> impl Iterator<Item = (u8, Option<&PartReport>)> {
+    reports.iter().flat_map(|r| match &r.parts {
+        Some(parts) => parts.iter().map(|p| (r.day, Some(p))).collect::<Vec<_>>(),
+        None => vec![(r.day, None)],
+    })
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
+
+pub fn to_json(reports: &[DayReport]) -> String {
+    let rows: Vec<String> = rows(reports)
+        .map(|(day, part)| match part {
+            Some(p) => format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"duration_ms\": {}, \"status\": \"{}\", \"message\": {}}}",
+                day,
+                p.part,
+                p.status.answer().map_or("null".into(), json_string),
+                p.status
+                    .elapsed_ms()
+                    .map_or("null".into(), |ms| format!("{:.3}", ms)),
+                p.status,
+                p.status
+                    .message()
+                    .map_or("null".into(), |m| json_string(&m)),
+            ),
+            None => format!(
+                "  {{\"day\": {}, \"part\": null, \"answer\": null, \"duration_ms\": null, \"status\": \"{}\", \"message\": {}}}",
+                day,
+                MISSING,
+                json_string(MISSING_MESSAGE),
+            ),
+        })
+        .collect();
+
+    if rows.is_empty() {
+        "[]".into()
+    } else {
+        format!("[\n{}\n]", rows.join(",\n"))
+    }
+}
+
+pub fn to_csv(reports: &[DayReport]) -> String {
+    let mut out = String::from("day,part,answer,duration_ms,status,message\n");
+    for (day, part) in rows(reports) {
+        out.push_str(&match part {
+            Some(p) => format!(
+                "{},{},{},{},{},{}\n",
+                day,
+                p.part,
+                csv_field(p.status.answer().unwrap_or_default()),
+                p.status
+                    .elapsed_ms()
+                    .map_or(String::new(), |ms| format!("{:.3}", ms)),
+                p.status,
+                csv_field(&p.status.message().unwrap_or_default()),
+            ),
+            None => format!("{},,,,{},{}\n", day, MISSING, MISSING_MESSAGE),
+        });
+    }
+    out
+}
+
+pub fn to_markdown(reports: &[DayReport]) -> String {
+    let mut out = String::from(
+        "| Day | Part | Answer | Time | Status |\n| :---: | :---: | :---: | ---: | :---: |\n",
+    );
+    for (day, part) in rows(reports) {
+        out.push_str(&match part {
+            Some(p) => format!(
+                "| {} | {} | {} | {} | {} |\n",
+                day,
+                p.part,
+                markdown_cell(p.status.answer().unwrap_or("-")),
+                p.status
+                    .elapsed_ms()
+                    .map_or("-".into(), |ms| format!("{:.2}ms", ms)),
+                markdown_cell(&match p.status.message() {
+                    Some(message) => format!("{} ({})", p.status, message),
+                    None => p.status.to_string(),
+                }),
+            ),
+            None => format!(
+                "| {} | - | - | - | {} ({}) |\n",
+                day, MISSING, MISSING_MESSAGE
+            ),
+        });
+    }
+    out
+}
+
+fn format_ms(ms: f64) -> String {
+    format!("{:.2?}", Duration::from_secs_f64(ms / 1000_f64))
+}
+
+/// Renders the per-day timing table written to the readme by `cargo all --update-readme`.
+pub fn to_benchmark_table(reports: &[DayReport]) -> String {
+    let mut out =
+        String::from("## Benchmarks\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n");
+    let mut total = 0_f64;
+
+    for report in reports {
+        let Some(parts) = &report.parts else {
+            out.push_str(&format!(
+                "| Day {} | {} | {} |\n",
+                report.day, MISSING, MISSING
+            ));
+            continue;
+        };
+
+        let cells: Vec<String> = parts
+            .iter()
+            .map(|p| match p.status.elapsed_ms() {
+                Some(ms) => {
+                    total += ms;
+                    format!("`{}`", format_ms(ms))
+                }
+                None => "-".into(),
+            })
+            .collect();
+
+        out.push_str(&format!(
+            "| [Day {}](./src/bin/{:02}.rs) | {} |\n",
+            report.day,
+            report.day,
+            cells.join(" | ")
+        ));
+    }
+
+    out.push_str(&format!("\n**Total: {:.2}ms**\n", total));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::Status;
+
+    fn part(part: u8, status: Status) -> PartReport {
+        PartReport {
+            day: 7,
+            part,
+            output: String::new(),
+            status,
+        }
+    }
+
+    fn reports() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                day: 7,
+                parts: Some(vec![
+                    part(
+                        1,
+                        Status::Solved {
+                            answer: "a,\"b\"".into(),
+                            elapsed_ms: 1.5,
+                        },
+                    ),
+                    part(2, Status::TimedOut(Duration::from_secs(1))),
+                ]),
+            },
+            DayReport {
+                day: 8,
+                parts: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            to_json(&reports()),
+            "[\n  {\"day\": 7, \"part\": 1, \"answer\": \"a,\\\"b\\\"\", \"duration_ms\": 1.500, \"status\": \"solved\", \"message\": null},\n  {\"day\": 7, \"part\": 2, \"answer\": null, \"duration_ms\": null, \"status\": \"timed_out\", \"message\": \"timed out after 1.00s\"},\n  {\"day\": 8, \"part\": null, \"answer\": null, \"duration_ms\": null, \"status\": \"missing\", \"message\": \"no solution binary\"}\n]"
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&reports()),
+            "day,part,answer,duration_ms,status,message\n7,1,\"a,\"\"b\"\"\",1.500,solved,\n7,2,,,timed_out,timed out after 1.00s\n8,,,,missing,no solution binary\n"
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        assert_eq!(
+            to_markdown(&reports()),
+            "| Day | Part | Answer | Time | Status |\n| :---: | :---: | :---: | ---: | :---: |\n\
+             | 7 | 1 | a,\"b\" | 1.50ms | solved |\n\
+             | 7 | 2 | - | - | timed_out (timed out after 1.00s) |\n\
+             | 8 | - | - | - | missing (no solution binary) |\n"
+        );
+
+        let panicked = vec![DayReport {
+            day: 7,
+            parts: Some(vec![part(1, Status::Panicked("a | b\nc".into()))]),
+        }];
+        assert!(to_markdown(&panicked).ends_with("| 7 | 1 | - | - | panicked (a \\| b<br>c) |\n"));
+    }
+
+    #[test]
+    fn test_to_benchmark_table() {
+        assert_eq!(
+            to_benchmark_table(&reports()),
+            "## Benchmarks\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n| [Day 7](./src/bin/07.rs) | `1.50ms` | - |\n| Day 8 | missing | missing |\n\n**Total: 1.50ms**\n"
+        );
+    }
+}
@@ -0,0 +1,391 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{parse_part_outputs, PartOutput, PART_ENV};
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Display,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Solved { answer: String, elapsed_ms: f64 },
+    Unsolved,
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Solved { .. } => write!(f, "solved"),
+            Status::Unsolved => write!(f, "unsolved"),
+            Status::Panicked(_) => write!(f, "panicked"),
+            Status::TimedOut(_) => write!(f, "timed_out"),
+        }
+    }
+}
+
+impl Status {
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Status::Solved { answer, .. } => Some(answer),
+            _ => None,
+        }
+    }
+
+    pub fn elapsed_ms(&self) -> Option<f64> {
+        match self {
+            Status::Solved { elapsed_ms, .. } => Some(*elapsed_ms),
+            _ => None,
+        }
+    }
+
+    /// Panic message or timeout description.
+    pub fn message(&self) -> Option<String> {
+        match self {
+            Status::Panicked(message) => Some(message.clone()),
+            Status::TimedOut(timeout) => Some(format!("timed out after {:.2?}", timeout)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    /// raw stdout of the solution, as printed by `solve!`.
+    pub output: String,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u8,
+    /// `None` if there is no solution binary for this day.
+    pub parts: Option<Vec<PartReport>>,
+}
+
+/// Directory containing the compiled solution binaries of the given profile.
+/// This is the directory of the running executable, or its sibling if the profiles differ.
+pub fn bin_dir(release: bool) -> PathBuf {
+    let profile = if release { "release" } else { "debug" };
+    let own_dir = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+
+    match own_dir {
+        Some(dir) if release == cfg!(not(debug_assertions)) => dir,
+        Some(dir) => dir.with_file_name(profile),
+        None => PathBuf::from("target").join(profile),
+    }
+}
+
+pub fn bin_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("{:02}{}", day, env::consts::EXE_SUFFIX))
+}
+
+/// Compiles the solution binaries of `days`, optimized if `release` is set.
+/// Days without a module are skipped. Other binaries are left alone: the runner can't replace itself on windows.
+pub fn build_all(days: &[u8], release: bool) -> bool {
+    let bins: Vec<String> = days
+        .iter()
+        .map(|day| format!("{:02}", day))
+        .filter(|day| Path::new(&format!("src/bin/{}.rs", day)).exists())
+        .collect();
+    if bins.is_empty() {
+        return true;
+    }
+
+    let mut args = vec!["build", "--quiet"];
+    for bin in &bins {
+        args.extend(["--bin", bin.as_str()]);
+    }
+    if release {
+        args.push("--release");
+    }
+
+    Command::new("cargo")
+        .args(&args)
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Extracts the message of the first panic from a captured stderr.
+pub fn parse_panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines().skip_while(|l| !l.contains("panicked at"));
+    let header = lines.next()?;
+
+    // rust < 1.73 prints the message inline: `panicked at 'msg', src/file.rs:1:1`.
+    if let Some((_, rest)) = header.split_once("panicked at '") {
+        if let Some((message, _)) = rest.rsplit_once("', ") {
+            return Some(message.to_string());
+        }
+    }
+
+    let message: Vec<_> = lines
+        .take_while(|l| !l.starts_with("note:") && !l.starts_with("stack backtrace:"))
+        .collect();
+
+    if message.is_empty() {
+        Some(header.to_string())
+    } else {
+        Some(message.join("\n"))
+    }
+}
+
+fn read_to_string(mut reader: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = vec![];
+        reader.read_to_end(&mut buf).ok();
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+/// Runs a single part of a day's solution in its own process, killing it after `timeout`.
+pub fn run_part(bin: &Path, day: u8, part: u8, timeout: Duration) -> PartReport {
+    let report = |output: String, status: Status| PartReport {
+        day,
+        part,
+        output,
+        status,
+    };
+
+    let mut child = match Command::new(bin)
+        .env(PART_ENV, part.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            return report(
+                String::new(),
+                Status::Panicked(format!("could not spawn \"{}\": {}", bin.display(), e)),
+            )
+        }
+    };
+
+    let stdout = read_to_string(child.stdout.take().unwrap());
+    let stderr = read_to_string(child.stderr.take().unwrap());
+
+    let started = Instant::now();
+    let exit_status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if started.elapsed() >= timeout => {
+                child.kill().ok();
+                child.wait().ok();
+                break None;
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                child.kill().ok();
+                return report(
+                    String::new(),
+                    Status::Panicked(format!("could not wait for solution: {}", e)),
+                );
+            }
+        }
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    let status = match exit_status {
+        None => Status::TimedOut(timeout),
+        Some(status) if !status.success() => Status::Panicked(
+            parse_panic_message(&stderr).unwrap_or_else(|| format!("exited with {}", status)),
+        ),
+        Some(_) => match parse_part_outputs(&stdout)
+            .into_iter()
+            .find(|p| p.part == part)
+        {
+            Some(PartOutput {
+                answer: Some(answer),
+                elapsed_ms,
+                ..
+            }) => Status::Solved { answer, elapsed_ms },
+            _ => Status::Unsolved,
+        },
+    };
+
+    report(stdout, status)
+}
+
+/// Whether the input of `day` in `inputs_dir` exists and is not empty.
+pub fn has_input(inputs_dir: &Path, day: u8) -> bool {
+    fs::metadata(inputs_dir.join(format!("{:02}.txt", day))).is_ok_and(|m| m.len() > 0)
+}
+
+/// Runs both parts of a day one after another.
+/// Without an input, the solution is not started and both parts are unsolved with an empty output.
+pub fn run_day(bin_dir: &Path, inputs_dir: &Path, day: u8, timeout: Duration) -> DayReport {
+    let bin = bin_path(bin_dir, day);
+    let parts = if !bin.exists() {
+        None
+    } else if !has_input(inputs_dir, day) {
+        Some(
+            (1..=2)
+                .map(|part| PartReport {
+                    day,
+                    part,
+                    output: String::new(),
+                    status: Status::Unsolved,
+                })
+                .collect(),
+        )
+    } else {
+        Some(
+            (1..=2)
+                .map(|part| run_part(&bin, day, part, timeout))
+                .collect(),
+        )
+    };
+    DayReport { day, parts }
+}
+
+/// Runs `days` on a pool of `jobs` worker threads.
+/// `on_report` is called for every day in the order of `days`, as soon as the day and all days before it have finished.
+pub fn run_days(
+    days: &[u8],
+    bin_dir: &Path,
+    inputs_dir: &Path,
+    jobs: usize,
+    timeout: Duration,
+    mut on_report: impl FnMut(&DayReport),
+) -> Vec<DayReport> {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            let tx = tx.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::SeqCst);
+                let Some(&day) = days.get(idx) else {
+                    break;
+                };
+                if tx
+                    .send((idx, run_day(bin_dir, inputs_dir, day, timeout)))
+                    .is_err()
+                {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut reports = Vec::with_capacity(days.len());
+
+        for (idx, report) in rx {
+            pending.insert(idx, report);
+            while let Some(report) = pending.remove(&reports.len()) {
+                on_report(&report);
+                reports.push(report);
+            }
+        }
+
+        reports
+    })
+}
+
+/// Re-runs all solved parts one at a time so their timings are not skewed by concurrently running days.
+pub fn retime(reports: &mut [DayReport], bin_dir: &Path, timeout: Duration) {
+    for day in reports.iter_mut() {
+        let bin = bin_path(bin_dir, day.day);
+        for part in day.parts.iter_mut().flatten() {
+            if matches!(part.status, Status::Solved { .. }) {
+                *part = run_part(&bin, part.day, part.part, timeout);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_panic_message() {
+        let stderr = "thread 'main' panicked at src/bin/07.rs:29:18:\nunexpected line\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(parse_panic_message(stderr), Some("unexpected line".into()));
+
+        let stderr = "thread 'main' panicked at 'unexpected line', src/bin/07.rs:29:18\nnote: run with `RUST_BACKTRACE=1`";
+        assert_eq!(parse_panic_message(stderr), Some("unexpected line".into()));
+
+        assert_eq!(parse_panic_message("warning: unused variable"), None);
+    }
+
+    /// Writes a fake solution for `day` that sleeps before printing `<day>` as its answer.
+    #[cfg(unix)]
+    fn fake_solution(dir: &Path, day: u8, sleep_ms: u64) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = bin_path(dir, day);
+        let script = format!(
+            "#!/bin/sh\nsleep {}\necho \"🎄 Part $AOC_PART 🎄\"\necho \"{} (elapsed: 1.00ms)\"\n",
+            sleep_ms as f64 / 1000.0,
+            day
+        );
+        std::fs::write(&bin, script).unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_days_in_order() {
+        let dir = env::temp_dir().join(format!("aoc-runner-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // earlier days take longer, so they finish after the ones queued behind them.
+        for (day, sleep_ms) in [(1, 300), (2, 200), (3, 0), (5, 100), (6, 0)] {
+            fake_solution(&dir, day, sleep_ms);
+        }
+        // day 6 has no input, day 5 an empty one.
+        for day in 1..=5 {
+            let input = if day == 5 { "" } else { "input" };
+            std::fs::write(dir.join(format!("{:02}.txt", day)), input).unwrap();
+        }
+
+        let days = [1, 2, 3, 4, 5, 6];
+        let mut seen = vec![];
+        let reports = run_days(&days, &dir, &dir, 3, Duration::from_secs(10), |report| {
+            seen.push(report.day)
+        });
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(seen, days);
+        assert_eq!(reports.iter().map(|r| r.day).collect::<Vec<_>>(), days);
+        // day 4 has no solution binary.
+        assert!(reports[3].parts.is_none());
+        for report in &reports[4..] {
+            let parts = report.parts.as_ref().unwrap();
+            assert!(parts.iter().all(|p| p.status == Status::Unsolved));
+        }
+        for report in &reports[..3] {
+            let parts = report.parts.as_ref().unwrap();
+            assert_eq!(parts.len(), 2);
+            let answer = report.day.to_string();
+            assert!(parts.iter().all(|p| p.status.answer() == Some(&answer)));
+        }
+    }
+}
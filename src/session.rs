@@ -0,0 +1,306 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{aoc_cli, config};
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    time::{Duration, SystemTime},
+};
+
+/// Overrides the website, e.g. `file:///tmp/aoc` to serve `/tmp/aoc/settings` when testing locally.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// Session cookies of the website expire after about a month.
+pub const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+#[derive(Debug)]
+pub enum SessionError {
+    NoHomeDir,
+    Missing(PathBuf),
+    Unreadable(PathBuf, io::Error),
+    Unwritable(PathBuf, io::Error),
+    Malformed,
+    Invalid,
+    Network(String),
+    Server(u16),
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::NoHomeDir => write!(f, "could not determine the home directory."),
+            SessionError::Missing(path) => write!(
+                f,
+                "no session cookie found at \"{}\". Run `cargo session set` to store one.",
+                path.display()
+            ),
+            SessionError::Unreadable(path, e) => {
+                write!(f, "could not read \"{}\": {}", path.display(), e)
+            }
+            SessionError::Unwritable(path, e) => {
+                write!(f, "could not write \"{}\": {}", path.display(), e)
+            }
+            SessionError::Malformed => write!(
+                f,
+                "the session cookie should be a hexadecimal string, copy the value of the `session` cookie."
+            ),
+            SessionError::Invalid => write!(
+                f,
+                "the session cookie is invalid or has expired. Log in again and run `cargo session set`."
+            ),
+            SessionError::Network(e) => write!(f, "could not reach the website: {}", e),
+            SessionError::Server(status) => {
+                write!(f, "the website responded with status {}.", status)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+pub fn base_url() -> String {
+    env::var(BASE_URL_ENV)
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|_| BASE_URL.into())
+}
+
+pub fn path() -> Result<PathBuf, SessionError> {
+    aoc_cli::session_path().ok_or(SessionError::NoHomeDir)
+}
+
+/// Accepts the bare token as well as `session=<token>` copied from the browser.
+pub fn normalize(token: &str) -> Result<String, SessionError> {
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(SessionError::Malformed);
+    }
+    Ok(token.to_string())
+}
+
+pub fn read() -> Result<String, SessionError> {
+    let path = path()?;
+    match fs::read_to_string(&path) {
+        Ok(token) => normalize(&token),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(SessionError::Missing(path)),
+        Err(e) => Err(SessionError::Unreadable(path, e)),
+    }
+}
+
+/// Checks the session file before calling aoc-cli, if one is configured in `aoc.toml`.
+/// Otherwise aoc-cli looks for the cookie itself, e.g. in `ADVENT_OF_CODE_SESSION`, and reports a missing one.
+pub fn precheck() -> Result<(), SessionError> {
+    match config::get().session_file {
+        Some(_) => read().map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// Writes the token to the session file, readable only by the current user.
+pub fn store(token: &str) -> Result<PathBuf, SessionError> {
+    let path = path()?;
+    let token = normalize(token)?;
+    let unwritable = |e| SessionError::Unwritable(path.clone(), e);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(unwritable)?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path).map_err(unwritable)?;
+
+    // `mode` only applies to new files, tighten the permissions of an existing one.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .map_err(unwritable)?;
+    }
+
+    file.write_all(token.as_bytes()).map_err(unwritable)?;
+    Ok(path)
+}
+
+/// Time since the session file was last written.
+pub fn age() -> Option<Duration> {
+    let modified = fs::metadata(path().ok()?).ok()?.modified().ok()?;
+    SystemTime::now().duration_since(modified).ok()
+}
+
+/// Whether other users can read the session file.
+pub fn is_exposed() -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Some(mode) = path()
+            .ok()
+            .and_then(|path| fs::metadata(path).ok())
+            .map(|m| m.permissions().mode())
+        {
+            return mode & 0o077 != 0;
+        }
+    }
+    false
+}
+
+/// Shows the first and last characters of the token only.
+pub fn mask(token: &str) -> String {
+    if token.len() <= 12 {
+        return "*".repeat(token.len());
+    }
+    format!("{}…{}", &token[..6], &token[token.len() - 6..])
+}
+
+/// Requests `path` from the website with `curl`, authenticated by `token`. Returns the status code and body.
+/// The status code is `None` for non-http urls.
+pub fn get(path: &str, token: &str) -> Result<(Option<u16>, String), SessionError> {
+    get_at(&base_url(), path, token)
+}
+
+/// Like [get], but requests `path` from `base` instead of [base_url].
+pub fn get_at(base: &str, path: &str, token: &str) -> Result<(Option<u16>, String), SessionError> {
+    let url = format!("{}{}", base, path);
+
+    // pass the cookie as config on stdin so it does not show up in the process list.
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--config",
+            "-",
+            "--write-out",
+            "\n%{http_code}",
+            &url,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| SessionError::Network(format!("could not call curl: {}", e)))?;
+
+    let cookie = format!("cookie = \"session={}\"\n", token);
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(cookie.as_bytes())
+        .map_err(|e| SessionError::Network(e.to_string()))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| SessionError::Network(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(SessionError::Network(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status = status.trim().parse().ok().filter(|status| *status > 0);
+    Ok((status, body.to_string()))
+}
+
+/// Name of the logged in user in a page of the website, e.g. `<div class="user">Alice <span class="star-count">`.
+pub fn parse_user(page: &str) -> Option<String> {
+    let (_, rest) = page.split_once("<div class=\"user\">")?;
+    let name = rest.split('<').next()?.trim();
+    Some(if name.is_empty() {
+        "(anonymous user)".into()
+    } else {
+        name.to_string()
+    })
+}
+
+/// Validates `token` by requesting the settings page, which is only served to logged in users.
+/// Returns the name of the user.
+pub fn check(token: &str) -> Result<String, SessionError> {
+    check_at(&base_url(), token)
+}
+
+/// Like [check], but validates against the website at `base`.
+pub fn check_at(base: &str, token: &str) -> Result<String, SessionError> {
+    let (status, body) = get_at(base, "/settings", token)?;
+    match status {
+        Some(status) if status >= 500 => Err(SessionError::Server(status)),
+        // logged out users are redirected to the front page.
+        Some(status) if (300..500).contains(&status) => Err(SessionError::Invalid),
+        _ => parse_user(&body).ok_or(SessionError::Invalid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(" session=53616c7465\n").unwrap(), "53616c7465");
+        assert_eq!(normalize("53616C7465").unwrap(), "53616C7465");
+        assert!(matches!(normalize(""), Err(SessionError::Malformed)));
+        assert!(matches!(
+            normalize("session=abc; path=/"),
+            Err(SessionError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn test_parse_user() {
+        let page = "<header><div class=\"user\">Alice <span class=\"star-count\">34*</span></div></header>";
+        assert_eq!(parse_user(page), Some("Alice".into()));
+        assert_eq!(parse_user("<a href=\"/auth/login\">[Log In]</a>"), None);
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("0123456789abcdef"), "012345…abcdef");
+        assert_eq!(mask("0123"), "****");
+    }
+
+    #[test]
+    fn test_check_against_mock() {
+        // the mock is served through curl, which is not installed everywhere.
+        if Command::new("curl").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let base = format!("file://{}", dir.display());
+
+        fs::write(
+            dir.join("settings"),
+            "<header><div class=\"user\">Alice <span class=\"star-count\">34*</span></div></header>",
+        )
+        .unwrap();
+        assert_eq!(check_at(&base, "0123abcd").unwrap(), "Alice");
+
+        // logged out users get a page without the user.
+        fs::write(dir.join("settings"), "<a href=\"/auth/login\">[Log In]</a>").unwrap();
+        assert!(matches!(
+            check_at(&base, "0123abcd"),
+            Err(SessionError::Invalid)
+        ));
+
+        fs::remove_file(dir.join("settings")).unwrap();
+        assert!(matches!(
+            check_at(&base, "0123abcd"),
+            Err(SessionError::Network(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,275 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::date;
+use std::{
+    fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Directory containing user-defined module templates, e.g. `templates/my_template.rs`.
+pub const TEMPLATES_DIR: &str = "templates";
+
+const HEADER: &str = r###"//! {{title}}
+//!
+//! <https://adventofcode.com/{{year}}/day/{{day}}>
+{{imports}}
+"###;
+
+const TESTS: &str = r###"
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{day}});
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{day}});
+        assert_eq!(part_two(&input), None);
+    }
+}
+"###;
+
+const MAIN: &str = r###"
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{day}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+"###;
+
+const PLAIN: &str = r###"
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    None
+}
+"###;
+
+const GRID: &str = r###"
+type Grid = Vec<Vec<char>>;
+
+pub fn parse(input: &str) -> Grid {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+pub fn neighbours(grid: &Grid, x: usize, y: usize) -> Vec<(usize, usize)> {
+    let mut result = vec![];
+    if x > 0 {
+        result.push((x - 1, y));
+    }
+    if y > 0 {
+        result.push((x, y - 1));
+    }
+    if x + 1 < grid[y].len() {
+        result.push((x + 1, y));
+    }
+    if y + 1 < grid.len() {
+        result.push((x, y + 1));
+    }
+    result
+}
+
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    let _grid = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    let _grid = parse(input);
+    None
+}
+"###;
+
+const GRAPH: &str = r###"
+use std::collections::{HashMap, HashSet, VecDeque};
+
+type Graph<'a> = HashMap<&'a str, Vec<&'a str>>;
+
+pub fn parse(input: &str) -> Graph<'_> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let (node, neighbours) = line.split_once(": ")?;
+            Some((node, neighbours.split(", ").collect()))
+        })
+        .collect()
+}
+
+/// Number of edges on the shortest path between `from` and `to`.
+pub fn bfs(graph: &Graph, from: &str, to: &str) -> Option<usize> {
+    let mut q = VecDeque::from([(from, 0)]);
+    let mut seen = HashSet::from([from]);
+
+    while let Some((curr, dist)) = q.pop_front() {
+        if curr == to {
+            return Some(dist);
+        }
+        for neighbour in graph.get(curr).into_iter().flatten() {
+            if seen.insert(neighbour) {
+                q.push_back((neighbour, dist + 1));
+            }
+        }
+    }
+    None
+}
+
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    let _graph = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    let _graph = parse(input);
+    None
+}
+"###;
+
+const SIMULATION: &str = r###"
+#[derive(Debug, Default)]
+pub struct State {
+    tick: usize,
+}
+
+impl State {
+    pub fn step(&mut self) {
+        self.tick += 1;
+    }
+
+    pub fn is_done(&self) -> bool {
+        true
+    }
+}
+
+pub fn parse(_input: &str) -> State {
+    State::default()
+}
+
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    let mut state = parse(input);
+    while !state.is_done() {
+        state.step();
+    }
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    let _state = parse(input);
+    None
+}
+"###;
+
+pub const BUILTIN_TEMPLATES: [&str; 4] = ["plain", "grid", "graph", "simulation"];
+
+/// Values substituted for the `{{day}}`, `{{day_padded}}`, `{{year}}`, `{{title}}`,
+/// `{{answer_type}}` and `{{imports}}` placeholders.
+pub struct Placeholders {
+    pub day: u8,
+    pub year: u16,
+    pub title: String,
+    pub answer_type: String,
+    /// paths rendered as `use <path>;` lines.
+    pub imports: Vec<String>,
+}
+
+impl Placeholders {
+    pub fn new(day: u8, year: u16) -> Self {
+        Placeholders {
+            day,
+            year,
+            title: format!("Day {}", day),
+            answer_type: "u32".into(),
+            imports: vec![],
+        }
+    }
+}
+
+pub fn builtin(name: &str) -> Option<String> {
+    let body = match name {
+        "plain" => PLAIN,
+        "grid" => GRID,
+        "graph" => GRAPH,
+        "simulation" => SIMULATION,
+        _ => return None,
+    };
+    Some(format!("{}{}{}{}", HEADER, body, MAIN, TESTS))
+}
+
+/// Loads `templates/<name>.rs` if present, otherwise the built-in template with this name.
+pub fn load(name: &str) -> Result<String, String> {
+    let path = format!("{}/{}.rs", TEMPLATES_DIR, name);
+    if let Ok(template) = fs::read_to_string(&path) {
+        return Ok(template);
+    }
+
+    builtin(name).ok_or_else(|| {
+        format!(
+            "unknown template \"{}\". Use one of [{}] or create \"{}\".",
+            name,
+            BUILTIN_TEMPLATES.join(", "),
+            path
+        )
+    })
+}
+
+pub fn render(template: &str, placeholders: &Placeholders) -> String {
+    let imports: String = placeholders
+        .imports
+        .iter()
+        .map(|path| format!("use {};\n", path))
+        .collect();
+
+    template
+        .replace("{{imports}}\n", &imports)
+        .replace("{{imports}}", &imports)
+        .replace("{{day}}", &placeholders.day.to_string())
+        .replace("{{day_padded}}", &format!("{:02}", placeholders.day))
+        .replace("{{year}}", &placeholders.year.to_string())
+        .replace("{{title}}", &placeholders.title)
+        .replace("{{answer_type}}", &placeholders.answer_type)
+}
+
+/// Year of the most recent Advent of Code event according to the system clock.
+pub fn current_year() -> u16 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let (year, month, _) = date::civil_from_days((secs / 86_400) as i64);
+
+    if month == 12 {
+        year as u16
+    } else {
+        year as u16 - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut placeholders = Placeholders::new(7, 2022);
+        placeholders.answer_type = "u64".into();
+        placeholders.imports = vec!["itertools::Itertools".into()];
+
+        let rendered = render(&builtin("plain").unwrap(), &placeholders);
+
+        assert!(rendered.starts_with(
+            "//! Day 7\n//!\n//! <https://adventofcode.com/2022/day/7>\nuse itertools::Itertools;\n"
+        ));
+        assert!(rendered.contains("pub fn part_one(input: &str) -> Option<u64> {"));
+        assert!(rendered.contains("advent_of_code::read_file(\"examples\", 7);"));
+        assert!(!rendered.contains("{{"));
+    }
+}